@@ -0,0 +1,188 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, anyhow};
+use clap::Args;
+use miniparse::ParseError;
+use miniparse::address::resolve_section;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct ApplyArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// File with one edit per line: `set section.key value`, `del section.key`, `del [section]`,
+    /// `rename section.key new_key` or `rename [section] new_section`. Blank lines and lines starting with # are skipped.
+    #[arg(long)]
+    script: PathBuf,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Target<'script> {
+    /// `section.key`, or just `key` for the global section
+    Key { section: Option<&'script str>, key: &'script str },
+    /// `[section]`
+    Section(&'script str),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Operation<'script> {
+    Set { target: Target<'script>, value: &'script str },
+    Delete(Target<'script>),
+    Rename { target: Target<'script>, to: &'script str },
+}
+
+fn parse_target(text: &str) -> Target<'_> {
+    if let Some(section) = text.strip_prefix('[').and_then(|text| text.strip_suffix(']')) {
+        return Target::Section(section);
+    }
+
+    match text.rsplit_once('.') {
+        Some((section, key)) => Target::Key { section: Some(section), key },
+        None => Target::Key { section: None, key: text },
+    }
+}
+
+fn parse_operation(line: &str) -> anyhow::Result<Operation<'_>> {
+    let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let rest = rest.trim_start();
+    let (target, argument) = rest
+        .split_once(char::is_whitespace)
+        .map_or((rest, ""), |(target, argument)| (target, argument.trim()));
+
+    if target.is_empty() {
+        return Err(anyhow!("`{command}` needs a section.key or [section] to work on"));
+    }
+    let target = parse_target(target);
+
+    match (command, argument) {
+        ("set", _) if matches!(target, Target::Section(_)) => Err(anyhow!("`set` needs a section.key, not a section")),
+        ("set", value) => Ok(Operation::Set { target, value }),
+        ("del" | "delete", "") => Ok(Operation::Delete(target)),
+        ("del" | "delete", _) => Err(anyhow!("`{command}` takes no value")),
+        ("rename", "") => Err(anyhow!("`rename` needs the new name")),
+        ("rename", to) => Ok(Operation::Rename { target, to }),
+        _ => Err(anyhow!("Unknown operation `{command}`, expected set, del or rename")),
+    }
+}
+
+fn resolve<'a>(contents: &'a str, section: Option<&'a str>) -> Result<Option<&'a str>, ParseError> {
+    section.map(|section| resolve_section(contents, section)).transpose()
+}
+
+/// Applies a single operation to the contents, returning the edited contents.
+fn apply(contents: &str, operation: &Operation<'_>) -> anyhow::Result<String> {
+    let edited = match operation {
+        Operation::Set {
+            target: Target::Key { section, key },
+            value,
+        } => Some(miniparse::edit::set(contents, resolve(contents, *section)?, key, value)?),
+        Operation::Set { .. } => unreachable!("Sections are rejected when parsing"),
+        Operation::Delete(Target::Key { section, key }) => {
+            let edited = miniparse::edit::delete_key(contents, resolve(contents, *section)?, key)?;
+            if edited.is_none() {
+                log::warn!("Nothing to delete for {key}");
+            }
+            edited
+        }
+        Operation::Delete(Target::Section(section)) => {
+            let edited = miniparse::edit::delete_section(contents, resolve_section(contents, section)?)?;
+            if edited.is_none() {
+                log::warn!("Nothing to delete for [{section}]");
+            }
+            edited
+        }
+        Operation::Rename {
+            target: Target::Key { section, key },
+            to,
+        } => Some(
+            miniparse::edit::rename_key(contents, resolve(contents, *section)?, key, to)?.ok_or_else(|| CliError::KeyNotFound(key.to_string()))?,
+        ),
+        Operation::Rename {
+            target: Target::Section(section),
+            to,
+        } => Some(
+            miniparse::edit::rename_section(contents, resolve_section(contents, section)?, to)?
+                .ok_or_else(|| CliError::SectionNotFound(section.to_string()))?,
+        ),
+    };
+
+    Ok(edited.unwrap_or_else(|| contents.to_string()))
+}
+
+pub fn run(args: ApplyArgs) -> anyhow::Result<()> {
+    dialect::require_ini("apply")?;
+
+    let script = fs::read_to_string(&args.script).with_context(|| args.script.display().to_string())?;
+
+    // Parse the whole script first, so a typo on the last line does not leave a half-applied script behind
+    let mut operations = Vec::new();
+    for (index, line) in script.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let operation = parse_operation(line).with_context(|| format!("{}:{}", args.script.display(), index + 1))?;
+        operations.push((index + 1, operation));
+    }
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+
+    let mut edited = contents.clone();
+    for (line_number, operation) in &operations {
+        edited = apply(&edited, operation).with_context(|| format!("{}:{line_number}", args.script.display()))?;
+    }
+
+    source.write(&contents, &edited, &args.write)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::apply::{Operation, Target, apply, parse_operation};
+
+    #[test]
+    fn operations_are_parsed() {
+        assert_eq!(
+            parse_operation("set db.replica.host  localhost").unwrap(),
+            Operation::Set {
+                target: Target::Key {
+                    section: Some("db.replica"),
+                    key: "host"
+                },
+                value: "localhost"
+            }
+        );
+        assert_eq!(parse_operation("del [db]").unwrap(), Operation::Delete(Target::Section("db")));
+        assert_eq!(
+            parse_operation("rename name title").unwrap(),
+            Operation::Rename {
+                target: Target::Key { section: None, key: "name" },
+                to: "title"
+            }
+        );
+        assert!(parse_operation("set [db] x").is_err());
+        assert!(parse_operation("rename db.host").is_err());
+        assert!(parse_operation("move db.host x").is_err());
+    }
+
+    #[test]
+    fn operations_are_applied_in_order() {
+        let mut contents = "name=x\n[db]\nhost=a\n[old]\nk=1\n".to_string();
+        for line in ["set db.port 5432", "rename db.host hostname", "del name", "rename [old] new"] {
+            contents = apply(&contents, &parse_operation(line).unwrap()).unwrap();
+        }
+
+        assert_eq!(contents, "[db]\nhostname=a\nport = 5432\n[new]\nk=1\n");
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args};
+use miniparse::address::resolve_section;
+use regex::Regex;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::IniSource;
+use crate::output::FoundValue;
+
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("assertion").required(true).args(["equals", "matches", "not_exists"])))]
+pub struct AssertArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section name, or dotted path like `remote.origin` for `[remote "origin"]`. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Key name
+    #[arg(short, long)]
+    key: String,
+
+    /// Assert that the key exists and has exactly this value
+    #[arg(long)]
+    equals: Option<String>,
+
+    /// Assert that the key exists and its value matches this regular expression
+    #[arg(long)]
+    matches: Option<Regex>,
+
+    /// Assert that the key does not exist
+    #[arg(long)]
+    not_exists: bool,
+}
+
+pub fn run(args: AssertArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+
+    let section = args.section.as_deref().map(|section| resolve_section(&contents, section)).transpose()?;
+    let value = dialect::selected(&contents).find(&contents, &args.key, section)?;
+    let path = FoundValue {
+        section: args.section.as_deref(),
+        key: &args.key,
+        value: "",
+    }
+    .path();
+
+    match value {
+        Some(value) if args.not_exists => Err(CliError::AssertionFailed(format!("Expected {path} not to exist, but it is {value:?}")).into()),
+        None if args.not_exists => Ok(()),
+        None => Err(CliError::KeyNotFound(path).into()),
+        Some(value) => {
+            if let Some(expected) = &args.equals
+                && value != expected
+            {
+                return Err(CliError::AssertionFailed(format!("Expected {path} to be {expected:?}, but it is {value:?}")).into());
+            }
+            if let Some(pattern) = &args.matches
+                && !pattern.is_match(value)
+            {
+                return Err(CliError::AssertionFailed(format!("Expected {path} to match {pattern}, but it is {value:?}")).into());
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, List, ListState, Paragraph, Wrap};
+
+use crate::dialect;
+use crate::input::IniSource;
+
+#[derive(Args, Debug)]
+pub struct BrowseArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+}
+
+/// Whether colors were disabled with the `NO_COLOR` environment variable, see https://no-color.org
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Name shown for the global section
+const GLOBAL_SECTION_LABEL: &str = "(global)";
+
+struct Section {
+    /// `None` for the global section
+    name: Option<String>,
+    entries: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Sections,
+    Keys,
+}
+
+struct Browser {
+    title: String,
+    sections: Vec<Section>,
+    focus: Focus,
+    /// Search query, matched case-insensitively against section names, keys and values
+    query: String,
+    searching: bool,
+    section_state: ListState,
+    key_state: ListState,
+}
+
+impl Browser {
+    fn new(title: String, ini_file: &miniparse::models::IniFile<'_>) -> Self {
+        let to_entries = |section: &miniparse::models::IniSection<'_>| {
+            section
+                .entries
+                .iter()
+                .map(|entry| (entry.key.to_string(), entry.value.to_string()))
+                .collect()
+        };
+
+        let mut sections = Vec::new();
+        if let Some(global_section) = ini_file.get_global_section() {
+            sections.push(Section {
+                name: None,
+                entries: to_entries(global_section),
+            });
+        }
+        sections.extend(ini_file.sections().map(|(name, section)| Section {
+            name: Some(name.to_string()),
+            entries: to_entries(section),
+        }));
+
+        Self {
+            title,
+            sections,
+            focus: Focus::Sections,
+            query: String::new(),
+            searching: false,
+            section_state: ListState::default().with_selected(Some(0)),
+            key_state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        text.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    fn section_matches(&self, section: &Section) -> bool {
+        section.name.as_deref().is_some_and(|name| self.matches(name))
+    }
+
+    /// Indices of the sections that match the search, or contain an entry that does.
+    fn visible_sections(&self) -> Vec<usize> {
+        (0..self.sections.len())
+            .filter(|&index| {
+                let section = &self.sections[index];
+                self.section_matches(section) || section.entries.iter().any(|(key, value)| self.matches(key) || self.matches(value))
+            })
+            .collect()
+    }
+
+    fn selected_section(&self) -> Option<&Section> {
+        let visible_sections = self.visible_sections();
+        let index = visible_sections.get(self.section_state.selected()?)?;
+        Some(&self.sections[*index])
+    }
+
+    /// The entries of the selected section that match the search. All of them when the section name itself matches.
+    fn visible_entries(&self) -> Vec<&(String, String)> {
+        let Some(section) = self.selected_section() else {
+            return Vec::new();
+        };
+
+        if self.section_matches(section) {
+            return section.entries.iter().collect();
+        }
+
+        section
+            .entries
+            .iter()
+            .filter(|(key, value)| self.matches(key) || self.matches(value))
+            .collect()
+    }
+
+    fn selected_entry(&self) -> Option<&(String, String)> {
+        self.visible_entries().get(self.key_state.selected()?).copied()
+    }
+
+    /// Keeps the selection within the visible items after the search or section changed.
+    fn clamp_selection(&mut self) {
+        let section_count = self.visible_sections().len();
+        let section = self.section_state.selected().unwrap_or_default().min(section_count.saturating_sub(1));
+        self.section_state.select((section_count > 0).then_some(section));
+
+        let key_count = self.visible_entries().len();
+        let key = self.key_state.selected().unwrap_or_default().min(key_count.saturating_sub(1));
+        self.key_state.select((key_count > 0).then_some(key));
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let state = match self.focus {
+            Focus::Sections => &mut self.section_state,
+            Focus::Keys => &mut self.key_state,
+        };
+        if down {
+            state.select_next();
+        } else {
+            state.select_previous();
+        }
+
+        if self.focus == Focus::Sections {
+            self.key_state.select(Some(0));
+        }
+        self.clamp_selection();
+    }
+
+    /// Handles a key press, returning false when the browser should close.
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.searching {
+            match code {
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.query.clear();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                }
+                KeyCode::Char(c) => self.query.push(c),
+                _ => (),
+            }
+            self.clamp_selection();
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(true),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(false),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter => self.focus = Focus::Keys,
+            KeyCode::Left | KeyCode::Char('h') => self.focus = Focus::Sections,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Sections => Focus::Keys,
+                    Focus::Keys => Focus::Sections,
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+
+    fn block(&self, title: &str, focus: Focus) -> Block<'static> {
+        let block = Block::bordered().title(title.to_string());
+        if self.focus != focus {
+            return block;
+        }
+
+        // Without colors, the focused pane is marked with a thick border instead
+        if no_color() {
+            block.border_type(BorderType::Thick)
+        } else {
+            block.border_style(Style::new().cyan())
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main_area, status_area] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [sections_area, keys_area, value_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(30), Constraint::Percentage(40)]).areas(main_area);
+
+        // Dotted section names are shown as a tree, indented by their depth
+        let section_items: Vec<String> = self
+            .visible_sections()
+            .into_iter()
+            .map(|index| match &self.sections[index].name {
+                Some(name) => {
+                    let depth = name.matches('.').count();
+                    format!("{}{}", "  ".repeat(depth), name.rsplit('.').next().unwrap_or(name))
+                }
+                None => GLOBAL_SECTION_LABEL.to_string(),
+            })
+            .collect();
+        let sections = List::new(section_items)
+            .block(self.block(&self.title, Focus::Sections))
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(sections, sections_area, &mut self.section_state);
+
+        let key_items: Vec<String> = self.visible_entries().into_iter().map(|(key, _)| key.clone()).collect();
+        let keys = List::new(key_items)
+            .block(self.block("Keys", Focus::Keys))
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(keys, keys_area, &mut self.key_state);
+
+        let value = self.selected_entry().map(|(_, value)| value.as_str()).unwrap_or_default();
+        let preview = Paragraph::new(value).wrap(Wrap { trim: false }).block(Block::bordered().title("Value"));
+        frame.render_widget(preview, value_area);
+
+        let status = if self.searching || !self.query.is_empty() {
+            Line::from(format!("/{}", self.query))
+        } else {
+            Line::from("q quit  / search  ←→ switch pane  ↑↓ move").dim()
+        };
+        frame.render_widget(status, status_area);
+    }
+}
+
+fn run_browser(terminal: &mut DefaultTerminal, browser: &mut Browser) -> anyhow::Result<()> {
+    loop {
+        terminal.draw(|frame| browser.draw(frame))?;
+
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && !browser.handle_key(key.code)
+        {
+            return Ok(());
+        }
+    }
+}
+
+pub fn run(args: BrowseArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let mut browser = Browser::new(source.name(), &ini_file);
+
+    let mut terminal = ratatui::init();
+    let result = run_browser(&mut terminal, &mut browser);
+    ratatui::restore();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyCode;
+
+    use crate::commands::browse::Browser;
+
+    fn browser(contents: &str) -> Browser {
+        Browser::new(String::new(), &miniparse::parse(contents).unwrap())
+    }
+
+    fn search(browser: &mut Browser, query: &str) {
+        browser.handle_key(KeyCode::Char('/'));
+        for c in query.chars() {
+            browser.handle_key(KeyCode::Char(c));
+        }
+        browser.handle_key(KeyCode::Enter);
+    }
+
+    #[test]
+    fn search_filters_sections_and_keys() {
+        let mut browser = browser("g=1\n[database]\nhost=localhost\nport=5432\n[cache]\nttl=60\n");
+        search(&mut browser, "PORT");
+
+        assert_eq!(browser.visible_sections(), vec![1]);
+        assert_eq!(browser.selected_entry(), Some(&("port".to_string(), "5432".to_string())));
+    }
+
+    #[test]
+    fn matching_section_shows_all_keys() {
+        let mut browser = browser("[database]\nhost=localhost\nport=5432\n[cache]\nttl=60\n");
+        search(&mut browser, "data");
+
+        assert_eq!(browser.visible_entries().len(), 2);
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use anstyle::{AnsiColor, Style};
+use clap::Args;
+
+use crate::input::IniSource;
+
+#[derive(Args, Debug)]
+pub struct CatArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Prefix every line with its line number
+    #[arg(short = 'n', long)]
+    line_numbers: bool,
+}
+
+const SECTION_STYLE: Style = AnsiColor::Blue.on_default().bold();
+const KEY_STYLE: Style = AnsiColor::Cyan.on_default();
+const VALUE_STYLE: Style = AnsiColor::Green.on_default();
+const COMMENT_STYLE: Style = Style::new().dimmed().italic();
+const LINE_NUMBER_STYLE: Style = Style::new().dimmed();
+
+/// Wraps the parts of a line in the style of what they are. Whitespace and delimiters are kept as they are.
+fn highlight(line: &str) -> String {
+    let trimmed = line.trim();
+
+    if trimmed.starts_with(['#', ';']) {
+        return format!("{COMMENT_STYLE}{line}{COMMENT_STYLE:#}");
+    }
+
+    if trimmed.starts_with('[') && trimmed.ends_with(']') {
+        return format!("{SECTION_STYLE}{line}{SECTION_STYLE:#}");
+    }
+
+    match line.split_once('=') {
+        Some((key, value)) => {
+            let key_start = key.len() - key.trim_start().len();
+            let key_end = key.trim_end().len();
+            let value_start = value.len() - value.trim_start().len();
+            let value_end = value.trim_end().len().max(value_start);
+            format!(
+                "{}{KEY_STYLE}{}{KEY_STYLE:#}{}={}{VALUE_STYLE}{}{VALUE_STYLE:#}{}",
+                &key[..key_start],
+                &key[key_start..key_end.max(key_start)],
+                &key[key_end.max(key_start)..],
+                &value[..value_start],
+                &value[value_start..value_end],
+                &value[value_end..],
+            )
+        }
+        None => line.to_string(),
+    }
+}
+
+pub fn run(args: CatArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+
+    let number_width = contents.lines().count().to_string().len();
+
+    // anstream strips the colors when stdout is not a terminal, or when NO_COLOR is set
+    for (index, line) in contents.lines().enumerate() {
+        if args.line_numbers {
+            anstream::print!("{LINE_NUMBER_STYLE}{:>number_width$}{LINE_NUMBER_STYLE:#} ", index + 1);
+        }
+        anstream::println!("{}", highlight(line));
+    }
+
+    Ok(())
+}
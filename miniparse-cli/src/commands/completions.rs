@@ -0,0 +1,17 @@
+use std::io;
+
+use clap::{Args, CommandFactory};
+use clap_complete::{Shell, generate};
+
+#[derive(Args, Debug)]
+pub struct CompletionsArgs {
+    /// Shell to generate the completion script for
+    #[arg(value_enum)]
+    shell: Shell,
+}
+
+pub fn run(args: CompletionsArgs) -> anyhow::Result<()> {
+    generate(args.shell, &mut crate::Args::command(), env!("CARGO_BIN_NAME"), &mut io::stdout());
+
+    Ok(())
+}
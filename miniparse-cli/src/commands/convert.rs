@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args, ValueEnum};
+use miniparse::convert::{Format, export, import};
+
+use crate::dialect;
+use crate::input::IniSource;
+use crate::output::write_output;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ConvertFormat {
+    Toml,
+    Json,
+    Yaml,
+    Env,
+}
+
+impl From<ConvertFormat> for Format {
+    fn from(format: ConvertFormat) -> Self {
+        match format {
+            ConvertFormat::Toml => Format::Toml,
+            ConvertFormat::Json => Format::Json,
+            ConvertFormat::Yaml => Format::Yaml,
+            ConvertFormat::Env => Format::Env,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("direction").required(true).args(["to", "from"])))]
+pub struct ConvertArgs {
+    /// File path of the input file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// File path to write the converted file to. Printed to stdout when left empty.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Convert the .ini file to this format
+    #[arg(long, value_enum)]
+    to: Option<ConvertFormat>,
+
+    /// Convert a file in this format to .ini
+    #[arg(long, value_enum)]
+    from: Option<ConvertFormat>,
+}
+
+pub fn run(args: ConvertArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+
+    let converted = match (args.to, args.from) {
+        (Some(to), _) => export(&dialect::parse(&source.read()?)?, to.into())?,
+        (None, Some(from)) => import(&source.read_file()?, from.into())?,
+        (None, None) => unreachable!("Either --to or --from is required"),
+    };
+
+    write_output(args.output.as_deref(), &converted)?;
+
+    Ok(())
+}
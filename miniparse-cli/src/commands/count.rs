@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::address::{resolve_section, resolve_section_ignore_case};
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::IniSource;
+
+#[derive(Args, Debug)]
+pub struct CountArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Count the keys of this section instead of the sections. Section name, or dotted path like `remote.origin` for
+    /// `[remote "origin"]`.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Count the keys of the global section instead of the sections
+    #[arg(short, long, conflicts_with = "section")]
+    global: bool,
+
+    /// Match the section name regardless of its case
+    #[arg(short, long)]
+    ignore_case: bool,
+}
+
+pub fn run(args: CountArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let section = match &args.section {
+        Some(section_name) => {
+            let resolved_name = if args.ignore_case {
+                resolve_section_ignore_case(&contents, section_name)?
+            } else {
+                resolve_section(&contents, section_name)?
+            };
+            let section = if args.ignore_case {
+                ini_file.get_section_by_name_ignore_case(resolved_name)
+            } else {
+                ini_file.get_section_by_name(resolved_name)
+            };
+            Some(section.ok_or_else(|| CliError::SectionNotFound(section_name.clone()))?)
+        }
+        None if args.global => ini_file.get_global_section(),
+        None => {
+            println!("{}", ini_file.sections().count());
+            return Ok(());
+        }
+    };
+
+    // Repeated keys are counted once, matching the output of list-keys
+    let mut keys: Vec<&str> = Vec::new();
+    for entry in section.iter().flat_map(|section| &section.entries) {
+        if !keys.contains(&entry.key) {
+            keys.push(entry.key);
+        }
+    }
+    println!("{}", keys.len());
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::Args;
+use miniparse::address::resolve_section;
+
+use crate::dialect;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct DeleteArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section name, or dotted path like `remote.origin` for `[remote "origin"]`. Without --key, the whole section is deleted. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Key name
+    #[arg(short, long)]
+    key: Option<String>,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+pub fn run(args: DeleteArgs) -> anyhow::Result<()> {
+    dialect::require_ini("delete")?;
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+
+    let section = args.section.as_deref().map(|section| resolve_section(&contents, section)).transpose()?;
+
+    let edited = match (&args.key, section) {
+        (Some(key), section) => miniparse::edit::delete_key(&contents, section, key)?,
+        (None, Some(section)) => miniparse::edit::delete_section(&contents, section)?,
+        (None, None) => return Err(anyhow!("Specify a --key, a --section, or both")),
+    };
+
+    match edited {
+        Some(edited) => source.write(&contents, &edited, &args.write)?,
+        None => log::warn!("Nothing to delete, the file was left untouched"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::diff::{Change, ChangeKind, diff};
+use serde_json::json;
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::input::read_ini_file;
+use crate::output::ReportFormat;
+use crate::secrets::{MASK, MaskArgs};
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// File path of the old .ini file
+    old: PathBuf,
+
+    /// File path of the new .ini file
+    new: PathBuf,
+
+    /// Output format of the differences [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<ReportFormat>,
+
+    #[command(flatten)]
+    mask_args: MaskArgs,
+}
+
+fn format_text(changes: &[Change<'_>]) -> String {
+    let mut output = String::new();
+    let mut current_section = None;
+
+    for (index, change) in changes.iter().enumerate() {
+        if (index == 0 || change.section != current_section)
+            && let Some(section_name) = change.section
+        {
+            output.push_str(&format!("[{section_name}]\n"));
+        }
+        current_section = change.section;
+
+        let line = match change.kind {
+            ChangeKind::Added { value } => format!("+ {} = {value}", change.key),
+            ChangeKind::Removed { value } => format!("- {} = {value}", change.key),
+            ChangeKind::Changed { old, new } => format!("~ {} = {old} -> {new}", change.key),
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
+
+fn format_json(changes: &[Change<'_>]) -> String {
+    let changes: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            let (kind, old, new) = match change.kind {
+                ChangeKind::Added { value } => ("added", None, Some(value)),
+                ChangeKind::Removed { value } => ("removed", Some(value), None),
+                ChangeKind::Changed { old, new } => ("changed", Some(old), Some(new)),
+            };
+            json!({ "section": change.section, "key": change.key, "change": kind, "old": old, "new": new })
+        })
+        .collect();
+
+    format!("{}\n", json!(changes))
+}
+
+pub fn run(args: DiffArgs) -> anyhow::Result<()> {
+    let old_contents = read_ini_file(&args.old)?;
+    let new_contents = read_ini_file(&args.new)?;
+
+    let old = dialect::parse(&old_contents)?;
+    let new = dialect::parse(&new_contents)?;
+
+    let mut changes = diff(&old, &new);
+
+    // Masked after comparing, so a changed secret is still reported as changed
+    let secret_keys = args.mask_args.secret_keys()?;
+    for change in changes.iter_mut().filter(|change| secret_keys.matches(change.key)) {
+        change.kind = match change.kind {
+            ChangeKind::Added { .. } => ChangeKind::Added { value: MASK },
+            ChangeKind::Removed { .. } => ChangeKind::Removed { value: MASK },
+            ChangeKind::Changed { .. } => ChangeKind::Changed { old: MASK, new: MASK },
+        };
+    }
+
+    match format_or_default(args.format, ReportFormat::Text) {
+        ReportFormat::Text => print!("{}", format_text(&changes)),
+        ReportFormat::Json => print!("{}", format_json(&changes)),
+    }
+
+    Ok(())
+}
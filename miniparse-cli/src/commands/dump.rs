@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::expand::expand;
+
+use crate::dialect;
+use crate::input::IniSource;
+use crate::input::env_var;
+use crate::output::write_output;
+use crate::secrets::MaskArgs;
+use crate::watch::watch;
+
+#[derive(Args, Debug)]
+pub struct DumpArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// File path to write the parsed file to. Printed to stdout when left empty.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Resolve `${section:key}` and `${ENV_VAR}` references in the values
+    #[arg(long)]
+    expand: bool,
+
+    #[command(flatten)]
+    mask_args: MaskArgs,
+
+    /// Keep running, and print the file again whenever it changes
+    #[arg(short, long)]
+    watch: bool,
+}
+
+fn print_parsed(source: &IniSource, args: &DumpArgs) -> anyhow::Result<()> {
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let expanded_values = if args.expand {
+        ini_file
+            .entries()
+            .map(|entry| expand(entry.value, &ini_file, env_var))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        Vec::new()
+    };
+    let mut ini_file = ini_file;
+    for (entry, expanded_value) in ini_file.entries_mut().zip(&expanded_values) {
+        entry.value = expanded_value;
+    }
+
+    args.mask_args.mask(&mut ini_file)?;
+    write_output(args.output.as_deref(), &ini_file.to_string())?;
+    Ok(())
+}
+
+pub fn run(args: DumpArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path.clone())?;
+
+    if args.watch {
+        return watch(std::slice::from_ref(&source), || print_parsed(&source, &args));
+    }
+
+    print_parsed(&source, &args)
+}
@@ -0,0 +1,196 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, anyhow};
+use clap::Args;
+use miniparse::ParseError;
+use miniparse::files::write_atomic;
+use miniparse::schema::IniSchema;
+
+use crate::dialect;
+use crate::input::read_ini_file;
+
+#[derive(Args, Debug)]
+pub struct EditArgs {
+    /// File path of the .ini file to edit
+    #[arg(short, long)]
+    path: PathBuf,
+
+    /// Also check the edited file against this schema before saving it
+    #[arg(long)]
+    schema: Option<PathBuf>,
+}
+
+/// Editors that open a file at a line when it is preceded by `+line`
+const LINE_ARGUMENT_EDITORS: [&str; 6] = ["vi", "vim", "nvim", "nano", "emacs", "micro"];
+
+/// What is wrong with the edited file, and the line to reopen the editor at
+#[derive(Debug, PartialEq, Eq)]
+struct Problem {
+    line: Option<usize>,
+    message: String,
+}
+
+/// Checks the edited contents strictly, and against the schema when there is one.
+fn find_problem(contents: &str, schema: Option<&IniSchema<'_>>) -> anyhow::Result<Option<Problem>> {
+    match miniparse::parse_strict(contents) {
+        Err(error @ ParseError::StrictViolation { line, .. }) => {
+            return Ok(Some(Problem {
+                line: Some(line),
+                message: error.to_string(),
+            }));
+        }
+        result => result?,
+    };
+
+    let violations = match schema {
+        Some(schema) => schema.validate(contents)?,
+        None => Vec::new(),
+    };
+    let Some(first_violation) = violations.first() else {
+        return Ok(None);
+    };
+
+    let messages = violations.iter().map(|violation| match violation.line {
+        Some(line) => format!("Line {line}: {violation}"),
+        None => violation.to_string(),
+    });
+
+    Ok(Some(Problem {
+        line: first_violation.line,
+        message: messages.collect::<Vec<_>>().join("\n"),
+    }))
+}
+
+/// The editor from `$VISUAL` or `$EDITOR`, falling back to `vi`. The variables may contain arguments, like `code --wait`.
+fn editor_command(editor: &str, path: &Path, line: Option<usize>) -> anyhow::Result<Command> {
+    let mut words = editor.split_whitespace();
+    let program = words.next().ok_or_else(|| anyhow!("$EDITOR is empty"))?;
+
+    let mut command = Command::new(program);
+    command.args(words);
+
+    let program_name = Path::new(program).file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    if let Some(line) = line
+        && LINE_ARGUMENT_EDITORS.contains(&program_name)
+    {
+        command.arg(format!("+{line}"));
+    }
+    command.arg(path);
+
+    Ok(command)
+}
+
+fn open_editor(path: &Path, line: Option<usize>) -> anyhow::Result<()> {
+    let editor = env::var("VISUAL").or_else(|_| env::var("EDITOR")).unwrap_or_else(|_| "vi".to_string());
+    let status = editor_command(&editor, path, line)?
+        .status()
+        .with_context(|| format!("Could not start {editor}"))?;
+
+    if !status.success() {
+        return Err(anyhow!("{editor} exited with {status}"));
+    }
+    Ok(())
+}
+
+/// Asks whether to edit again after a problem was found. Anything but `x` edits again, unless stdin was closed.
+fn edit_again() -> io::Result<bool> {
+    eprint!("What now? (e)dit again, e(x)it without saving [e]: ");
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    let read = io::stdin().read_line(&mut answer)?;
+    Ok(read > 0 && !answer.trim().eq_ignore_ascii_case("x"))
+}
+
+/// Edits the copy until it is valid, returning the valid contents, or `None` when the user gave up.
+fn edit_until_valid(copy: &Path, schema: Option<&IniSchema<'_>>) -> anyhow::Result<Option<String>> {
+    let mut line = None;
+
+    loop {
+        open_editor(copy, line)?;
+        let contents = fs::read_to_string(copy)?;
+
+        let Some(problem) = find_problem(&contents, schema)? else {
+            return Ok(Some(contents));
+        };
+
+        eprintln!("{}", problem.message);
+        if !edit_again()? {
+            return Ok(None);
+        }
+        line = problem.line;
+    }
+}
+
+pub fn run(args: EditArgs) -> anyhow::Result<()> {
+    dialect::require_ini("edit")?;
+
+    let schema_contents = args.schema.as_deref().map(read_ini_file).transpose()?;
+    let schema = schema_contents.as_deref().map(IniSchema::parse).transpose()?;
+
+    let original = read_ini_file(&args.path).with_context(|| args.path.display().to_string())?;
+
+    // Like visudo, the file itself is only written once the edits are valid. The copy ends in the file name, so editors
+    // still recognize it as an .ini file. It gets a random name and is only readable by the user, and is removed when
+    // dropped.
+    let file_name = args.path.file_name().ok_or_else(|| anyhow!("{} is not a file", args.path.display()))?;
+    let mut copy = tempfile::Builder::new()
+        .prefix("miniparse-edit-")
+        .suffix(&format!("-{}", file_name.to_string_lossy()))
+        .tempfile()?;
+    copy.write_all(original.as_bytes())?;
+    copy.flush()?;
+
+    let edited = edit_until_valid(copy.path(), schema.as_ref());
+
+    match edited? {
+        Some(edited) if edited == original => log::warn!("No changes made to {}", args.path.display()),
+        Some(edited) => write_atomic(&args.path, &edited)?,
+        None => return Err(anyhow!("{} was left unchanged", args.path.display())),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use miniparse::schema::IniSchema;
+
+    use crate::commands::edit::{Problem, editor_command, find_problem};
+
+    #[test]
+    fn problems_point_at_their_line() {
+        assert_eq!(find_problem("a=1\n[s]\nb=2\n", None).unwrap(), None);
+
+        let problem = find_problem("a=1\nnot an entry\n", None).unwrap().unwrap();
+        assert_eq!(problem.line, Some(2));
+
+        let schema = IniSchema::parse("[s]\nport = int\n").unwrap();
+        assert_eq!(
+            find_problem("[s]\nport=eighty\n", Some(&schema)).unwrap(),
+            Some(Problem {
+                line: Some(2),
+                message: "Line 2: Key port in [s] should be of type int, found \"eighty\"".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn editor_is_opened_at_the_line_when_supported() {
+        let path = Path::new("/tmp/app.ini");
+
+        let command = editor_command("/usr/bin/vim", path, Some(3)).unwrap();
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["+3", "/tmp/app.ini"]);
+
+        let command = editor_command("code --wait", path, Some(3)).unwrap();
+        assert_eq!(command.get_program(), "code");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["--wait", "/tmp/app.ini"]);
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::address::{resolve_section, resolve_section_ignore_case};
+
+use crate::dialect;
+use crate::error::{CliError, quiet};
+use crate::input::IniSource;
+
+#[derive(Args, Debug)]
+pub struct ExistsArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section name, or dotted path like `remote.origin` for `[remote "origin"]`. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Key name. Leave empty to only check whether the section exists.
+    #[arg(short, long, required_unless_present = "section")]
+    key: Option<String>,
+
+    /// Match section names and keys regardless of their case
+    #[arg(short, long)]
+    ignore_case: bool,
+}
+
+fn names_equal(a: &str, b: &str, ignore_case: bool) -> bool {
+    if ignore_case { a.to_lowercase() == b.to_lowercase() } else { a == b }
+}
+
+fn check(args: &ExistsArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path.clone())?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let section = match &args.section {
+        Some(section_name) => {
+            let resolved_name = if args.ignore_case {
+                resolve_section_ignore_case(&contents, section_name)?
+            } else {
+                resolve_section(&contents, section_name)?
+            };
+            let section = if args.ignore_case {
+                ini_file.get_section_by_name_ignore_case(resolved_name)
+            } else {
+                ini_file.get_section_by_name(resolved_name)
+            };
+            Some(section.ok_or_else(|| CliError::SectionNotFound(section_name.clone()))?)
+        }
+        None => ini_file.get_global_section(),
+    };
+
+    if let Some(key) = &args.key {
+        let found = section.is_some_and(|section| section.entries.iter().any(|entry| names_equal(entry.key, key, args.ignore_case)));
+        if !found {
+            return Err(CliError::KeyNotFound(key.clone()).into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn run(args: ExistsArgs) -> anyhow::Result<()> {
+    check(&args).map_err(quiet)
+}
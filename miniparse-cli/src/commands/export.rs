@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::address::resolve_section;
+use miniparse::convert::shell_quote;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::IniSource;
+use crate::output::to_variable_name;
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section to export, or dotted path like `remote.origin` for `[remote "origin"]`. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Prefix to put in front of every variable name, like DB_
+    #[arg(long, default_value = "")]
+    prefix: String,
+}
+
+pub fn run(args: ExportArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let section = match &args.section {
+        Some(section_name) => ini_file
+            .get_section_by_name(resolve_section(&contents, section_name)?)
+            .ok_or_else(|| CliError::SectionNotFound(section_name.clone()))?,
+        None => match ini_file.get_global_section() {
+            Some(global_section) => global_section,
+            None => return Ok(()),
+        },
+    };
+
+    for (index, entry) in section.entries.iter().enumerate() {
+        // Only the first occurrence of a repeated key is exported, like `get` prints
+        if section.entries[..index].iter().any(|previous| previous.key == entry.key) {
+            continue;
+        }
+
+        let name = to_variable_name(&format!("{}{}", args.prefix, entry.key)).to_uppercase();
+        println!("export {name}={}", shell_quote(entry.value));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct FmtArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Only check whether the file is formatted, without rewriting it
+    #[arg(long)]
+    check: bool,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+pub fn run(args: FmtArgs) -> anyhow::Result<()> {
+    dialect::require_ini("fmt")?;
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+
+    let formatted = miniparse::format::format(&contents)?;
+
+    if args.check {
+        if formatted != contents {
+            return Err(CliError::NotFormatted(source.name()).into());
+        }
+        return Ok(());
+    }
+
+    source.write(&contents, &formatted, &args.write)?;
+
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::{Args, ValueEnum};
+use miniparse::address::{resolve_section, resolve_section_ignore_case};
+use miniparse::expand::expand;
+use miniparse::schema::{DeprecationWarning, IniSchema};
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::error::{CliError, quiet};
+use crate::input::{IniSource, env_var, find_ini_files, for_each_source, read_ini_file};
+use crate::output::{FoundValue, OutputFormat, format_values, format_values_per_file};
+use crate::value_type::ValueType;
+use crate::watch::watch;
+
+#[derive(Args, Debug)]
+pub struct GetArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin. Can be repeated, or be a glob like
+    /// `configs/*.ini`, to look up the keys in multiple files.
+    #[arg(short, long)]
+    path: Vec<PathBuf>,
+
+    /// Look up the keys in every .ini file in this directory and its subdirectories
+    #[arg(short, long)]
+    recursive: Option<PathBuf>,
+
+    /// Keep running, and print the values again whenever the file changes
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Section name, or dotted path like `remote.origin` for `[remote "origin"]`. Leave empty for global section. Repeat once per --key to look up keys in different sections.
+    #[arg(short, long)]
+    section: Vec<String>,
+
+    /// Key name. Can be repeated to look up multiple keys at once.
+    #[arg(short, long, required = true)]
+    key: Vec<String>,
+
+    /// Match section names and keys regardless of their case
+    #[arg(short, long)]
+    ignore_case: bool,
+
+    /// Print every occurrence of a repeated key, instead of only the first one
+    #[arg(short, long)]
+    all: bool,
+
+    /// Value to print when a key is not found, instead of failing
+    #[arg(short, long)]
+    default: Option<String>,
+
+    /// Validate the found values as this type, and print them in normalized form
+    #[arg(short, long, value_enum)]
+    r#type: Option<ValueType>,
+
+    /// Output format of the found values [default: raw]
+    #[arg(short, long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Resolve `${section:key}` and `${ENV_VAR}` references in the values
+    #[arg(long)]
+    expand: bool,
+
+    /// Schema declaring deprecated keys. A key that is missing is then read from the deprecated key it replaces, with a
+    /// warning.
+    #[arg(long)]
+    schema: Option<PathBuf>,
+
+    /// Print nothing, and only report through the exit code whether all keys were found
+    #[arg(short, long, conflicts_with_all = ["watch", "format"])]
+    quiet: bool,
+}
+
+/// Pairs every key with the section it should be looked up in.
+fn pair_sections_with_keys<'a>(sections: &'a [String], keys: &'a [String]) -> anyhow::Result<Vec<(Option<&'a str>, &'a str)>> {
+    match sections {
+        [] => Ok(keys.iter().map(|key| (None, key.as_str())).collect()),
+        [section] => Ok(keys.iter().map(|key| (Some(section.as_str()), key.as_str())).collect()),
+        _ if sections.len() == keys.len() => Ok(sections
+            .iter()
+            .map(|section| Some(section.as_str()))
+            .zip(keys.iter().map(String::as_str))
+            .collect()),
+        _ => Err(anyhow!("Pass either a single --section, or one --section per --key")),
+    }
+}
+
+/// Tells apart a missing section from a missing key, so they can be reported with different exit codes.
+fn not_found_error(contents: &str, section: Option<&str>, key: &str, ignore_case: bool) -> anyhow::Result<CliError> {
+    if let Some(section_name) = section {
+        let ini_file = dialect::parse(contents)?;
+        let section_name = if ignore_case {
+            resolve_section_ignore_case(contents, section_name)?
+        } else {
+            resolve_section(contents, section_name)?
+        };
+        let found_section = if ignore_case {
+            ini_file.get_section_by_name_ignore_case(section_name)
+        } else {
+            ini_file.get_section_by_name(section_name)
+        };
+
+        if found_section.is_none() {
+            return Ok(CliError::SectionNotFound(section_name.to_string()));
+        }
+    }
+
+    Ok(CliError::KeyNotFound(FoundValue { section, key, value: "" }.path()))
+}
+
+/// Looks up the keys in the contents of a single file, returning the values coerced to the requested type.
+fn look_up<'a>(args: &'a GetArgs, contents: &str) -> anyhow::Result<Vec<(Option<&'a str>, &'a str, String)>> {
+    dialect::check(contents)?;
+    let ini_file = args.expand.then(|| dialect::parse(contents)).transpose()?;
+    let file_dialect = dialect::selected(contents);
+    let schema_contents = args.schema.as_deref().map(read_ini_file).transpose()?;
+    let schema = schema_contents.as_deref().map(IniSchema::parse).transpose()?;
+
+    let find_values = |key: &str, section_name: Option<&str>| {
+        if args.ignore_case {
+            file_dialect.find_all_ignore_case(contents, key, section_name)
+        } else if args.all {
+            file_dialect.find_all(contents, key, section_name)
+        } else {
+            file_dialect.find(contents, key, section_name).map(|value| value.into_iter().collect())
+        }
+    };
+
+    let mut results = Vec::new();
+
+    for (section, key) in pair_sections_with_keys(&args.section, &args.key)? {
+        let resolve = if args.ignore_case {
+            resolve_section_ignore_case
+        } else {
+            resolve_section
+        };
+        let section_name = section.map(|section| resolve(contents, section)).transpose()?;
+
+        let mut values = find_values(key, section_name)?;
+        if values.is_empty()
+            && let Some(schema) = &schema
+        {
+            for deprecated_key in schema.deprecated_keys(section_name, key) {
+                values = find_values(deprecated_key, section_name)?;
+                if !values.is_empty() {
+                    let warning = DeprecationWarning {
+                        line: None,
+                        section: section_name.map(str::to_string),
+                        key: deprecated_key.to_string(),
+                        replacement: key.to_string(),
+                    };
+                    log::warn!("{warning}");
+                    break;
+                }
+            }
+        }
+        if !args.all {
+            values.truncate(1);
+        }
+
+        if values.is_empty() {
+            match &args.default {
+                Some(default) => values.push(default.as_str()),
+                None => return Err(not_found_error(contents, section, key, args.ignore_case)?.into()),
+            }
+        }
+
+        for value in values {
+            let expanded_value;
+            let value = match &ini_file {
+                Some(ini_file) => {
+                    expanded_value = expand(value, ini_file, env_var)?;
+                    expanded_value.as_str()
+                }
+                None => value,
+            };
+
+            let coerced_value = match args.r#type {
+                Some(value_type) => value_type.coerce(value).map_err(|reason| {
+                    let path = FoundValue { section, key, value }.path();
+                    let type_name = value_type.to_possible_value().map(|possible_value| possible_value.get_name().to_string());
+                    anyhow!("The value {value:?} of {path} is not a valid {}: {reason}", type_name.unwrap_or_default())
+                })?,
+                None => value.to_string(),
+            };
+
+            results.push((section, key, coerced_value));
+        }
+    }
+
+    Ok(results)
+}
+
+fn to_found_values<'a>(results: &'a [(Option<&'a str>, &'a str, String)]) -> Vec<FoundValue<'a>> {
+    results
+        .iter()
+        .map(|(section, key, value)| FoundValue {
+            section: *section,
+            key,
+            value,
+        })
+        .collect()
+}
+
+fn print_values(args: &GetArgs, sources: &[IniSource]) -> anyhow::Result<()> {
+    let format = format_or_default(args.format, OutputFormat::Raw);
+
+    if let [source] = sources {
+        let results = look_up(args, &source.read()?)?;
+        print!("{}", format_values(format, &to_found_values(&results)));
+        return Ok(());
+    }
+
+    let mut results_per_file = Vec::new();
+    let outcome = for_each_source(sources, |source, contents| {
+        results_per_file.push((source.name(), look_up(args, contents)?));
+        Ok(())
+    });
+
+    let found_values_per_file: Vec<_> = results_per_file
+        .iter()
+        .map(|(name, results)| (name.as_str(), to_found_values(results)))
+        .collect();
+    print!("{}", format_values_per_file(format, &found_values_per_file));
+
+    outcome
+}
+
+/// Looks up the keys in every file without printing them, stopping at the first failure.
+fn check_values(args: &GetArgs, sources: &[IniSource]) -> anyhow::Result<()> {
+    for source in sources {
+        look_up(args, &source.read()?)?;
+    }
+    Ok(())
+}
+
+pub fn run(args: GetArgs) -> anyhow::Result<()> {
+    let mut paths = args.path.clone();
+    if let Some(dir) = &args.recursive {
+        paths.extend(find_ini_files(dir)?);
+    }
+    let sources = IniSource::from_path_args(paths)?;
+
+    if args.quiet {
+        return check_values(&args, &sources).map_err(quiet);
+    }
+
+    if args.watch {
+        return watch(&sources, || print_values(&args, &sources));
+    }
+
+    print_values(&args, &sources)
+}
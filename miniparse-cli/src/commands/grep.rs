@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+use regex::RegexBuilder;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::{IniSource, find_ini_files, for_each_source};
+use crate::output::FoundValue;
+
+#[derive(Args, Debug)]
+pub struct GrepArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin. Can be repeated, or be a glob.
+    #[arg(short, long)]
+    path: Vec<PathBuf>,
+
+    /// Search every .ini file in this directory and its subdirectories
+    #[arg(short, long)]
+    recursive: Option<PathBuf>,
+
+    /// Regular expression to search for
+    #[arg(short = 'e', long)]
+    pattern: String,
+
+    /// Only search keys
+    #[arg(short, long, conflicts_with = "values")]
+    keys: bool,
+
+    /// Only search values
+    #[arg(short, long)]
+    values: bool,
+
+    /// Match the pattern regardless of case
+    #[arg(short, long)]
+    ignore_case: bool,
+}
+
+pub fn run(args: GrepArgs) -> anyhow::Result<()> {
+    let regex = RegexBuilder::new(&args.pattern)
+        .case_insensitive(args.ignore_case)
+        .build()
+        .with_context(|| format!("Invalid pattern {:?}", args.pattern))?;
+
+    let mut paths = args.path;
+    if let Some(dir) = &args.recursive {
+        paths.extend(find_ini_files(dir)?);
+    }
+    let sources = IniSource::from_path_args(paths)?;
+
+    let mut match_count = 0;
+
+    // Searching the parsed file instead of the raw text means comments and unparsable lines never match
+    let mut grep = |source: &IniSource, contents: &str| -> anyhow::Result<()> {
+        let ini_file = dialect::parse(contents)?;
+        let sections = ini_file
+            .get_global_section()
+            .map(|section| (None, section))
+            .into_iter()
+            .chain(ini_file.sections().map(|(name, section)| (Some(name), section)));
+
+        for (section, ini_section) in sections {
+            for entry in &ini_section.entries {
+                let key_matches = !args.values && regex.is_match(entry.key);
+                let value_matches = !args.keys && regex.is_match(entry.value);
+
+                if key_matches || value_matches {
+                    let found = FoundValue {
+                        section,
+                        key: entry.key,
+                        value: entry.value,
+                    };
+                    println!("{}:{}={}", source.name(), found.path(), entry.value);
+                    match_count += 1;
+                }
+            }
+        }
+
+        Ok(())
+    };
+
+    match sources.as_slice() {
+        [source] => grep(source, &source.read()?)?,
+        _ => for_each_source(&sources, &mut grep)?,
+    }
+
+    if match_count == 0 {
+        return Err(CliError::KeyNotFound(args.pattern).into());
+    }
+
+    Ok(())
+}
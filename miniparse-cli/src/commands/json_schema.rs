@@ -0,0 +1,37 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::json_schema::{export, import};
+use miniparse::schema::IniSchema;
+
+use crate::input::IniSource;
+use crate::output::write_output;
+
+#[derive(Args, Debug)]
+pub struct JsonSchemaArgs {
+    /// File path of the schema, or of the JSON Schema with --import. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// File path to write the result to. Printed to stdout when left empty.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Convert a JSON Schema to a schema for `validate`, instead of the other way around
+    #[arg(long)]
+    import: bool,
+}
+
+pub fn run(args: JsonSchemaArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+
+    let converted = if args.import {
+        import(&source.read_file()?)?
+    } else {
+        export(&IniSchema::parse(&source.read()?)?)
+    };
+
+    write_output(args.output.as_deref(), &converted)?;
+
+    Ok(())
+}
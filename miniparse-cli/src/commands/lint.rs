@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use miniparse::lint::{LintRule, lint};
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::{IniSource, find_ini_files, for_each_source};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DeniedRule {
+    /// Every rule
+    All,
+    DuplicateKey,
+    EmptySection,
+    MixedDelimiters,
+    UnparsableLine,
+}
+
+impl DeniedRule {
+    fn denies(self, rule: LintRule) -> bool {
+        match self {
+            Self::All => true,
+            Self::DuplicateKey => rule == LintRule::DuplicateKey,
+            Self::EmptySection => rule == LintRule::EmptySection,
+            Self::MixedDelimiters => rule == LintRule::MixedDelimiters,
+            Self::UnparsableLine => rule == LintRule::UnparsableLine,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct LintArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin. Can be repeated, or be a glob.
+    #[arg(short, long)]
+    path: Vec<PathBuf>,
+
+    /// Lint every .ini file in this directory and its subdirectories
+    #[arg(short, long)]
+    recursive: Option<PathBuf>,
+
+    /// Fail when this rule has findings, instead of only printing them. Can be repeated.
+    #[arg(short, long, value_enum)]
+    deny: Vec<DeniedRule>,
+}
+
+pub fn run(args: LintArgs) -> anyhow::Result<()> {
+    dialect::require_ini("lint")?;
+
+    let mut paths = args.path;
+    if let Some(dir) = &args.recursive {
+        paths.extend(find_ini_files(dir)?);
+    }
+    let sources = IniSource::from_path_args(paths)?;
+
+    let lint_source = |source: &IniSource, contents: &str| -> anyhow::Result<()> {
+        let findings = lint(contents)?;
+        let mut denied_count = 0;
+
+        for finding in &findings {
+            let denied = args.deny.iter().any(|denied_rule| denied_rule.denies(finding.rule));
+            let level = if denied { "error" } else { "warning" };
+            println!("{}:{}: {level}: {finding}", source.name(), finding.line);
+
+            if denied {
+                denied_count += 1;
+            }
+        }
+
+        if denied_count > 0 {
+            return Err(CliError::LintFailed(denied_count).into());
+        }
+
+        Ok(())
+    };
+
+    match sources.as_slice() {
+        [source] => lint_source(source, &source.read()?),
+        _ => for_each_source(&sources, lint_source),
+    }
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::address::{resolve_section, resolve_section_ignore_case};
+
+use serde_json::Value;
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::IniSource;
+use crate::output::{ListFormat, entries_object, format_names};
+
+#[derive(Args, Debug)]
+pub struct ListKeysArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section name, or dotted path like `remote.origin` for `[remote "origin"]`. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Match the section name regardless of its case
+    #[arg(short, long)]
+    ignore_case: bool,
+
+    /// Output format of the keys [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<ListFormat>,
+}
+
+pub fn run(args: ListKeysArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let format = format_or_default(args.format, ListFormat::Text);
+
+    let entries = match &args.section {
+        Some(section_name) => {
+            let resolved_name = if args.ignore_case {
+                resolve_section_ignore_case(&contents, section_name)?
+            } else {
+                resolve_section(&contents, section_name)?
+            };
+            let section = if args.ignore_case {
+                ini_file.get_section_by_name_ignore_case(resolved_name)
+            } else {
+                ini_file.get_section_by_name(resolved_name)
+            };
+            section.ok_or_else(|| CliError::SectionNotFound(section_name.clone()))?.entries.as_slice()
+        }
+        None => ini_file
+            .get_global_section()
+            .map(|section| section.entries.as_slice())
+            .unwrap_or_default(),
+    };
+
+    if let ListFormat::JsonObject = format {
+        println!("{}", Value::Object(entries_object(entries)));
+        return Ok(());
+    }
+
+    let mut keys: Vec<&str> = Vec::new();
+    for entry in entries {
+        if !keys.contains(&entry.key) {
+            keys.push(entry.key);
+        }
+    }
+
+    print!("{}", format_names(format, &keys));
+
+    Ok(())
+}
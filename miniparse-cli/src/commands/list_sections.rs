@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use serde_json::{Map, Value};
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::input::IniSource;
+use crate::output::{ListFormat, entries_object, format_names};
+
+#[derive(Args, Debug)]
+pub struct ListSectionsArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Output format of the sections [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<ListFormat>,
+}
+
+pub fn run(args: ListSectionsArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let format = format_or_default(args.format, ListFormat::Text);
+
+    if let ListFormat::JsonObject = format {
+        let object: Map<String, Value> = ini_file
+            .sections()
+            .map(|(name, section)| (name.to_string(), Value::Object(entries_object(&section.entries))))
+            .collect();
+        println!("{}", Value::Object(object));
+        return Ok(());
+    }
+
+    let names: Vec<&str> = ini_file.sections().map(|(name, _)| name).collect();
+    print!("{}", format_names(format, &names));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::{fs, io, path::PathBuf};
+
+use clap::{Args, CommandFactory};
+use clap_mangen::Man;
+
+#[derive(Args, Debug)]
+pub struct ManArgs {
+    /// Directory to write a man page for the main command and one for every subcommand to, like `miniparse-get.1`.
+    /// Only the page of the main command is printed to stdout when left empty.
+    #[arg(short, long)]
+    output_dir: Option<PathBuf>,
+}
+
+pub fn run(args: ManArgs) -> anyhow::Result<()> {
+    let mut command = crate::Args::command().name(env!("CARGO_BIN_NAME"));
+    // Builds the subcommands too, so they include the global flags and can be rendered on their own
+    command.build();
+
+    let Some(output_dir) = args.output_dir else {
+        Man::new(command).render(&mut io::stdout())?;
+        return Ok(());
+    };
+
+    fs::create_dir_all(&output_dir)?;
+
+    let subcommands: Vec<_> = command
+        .get_subcommands()
+        .filter(|subcommand| subcommand.get_name() != "help")
+        .map(|subcommand| subcommand.clone().name(format!("{}-{}", env!("CARGO_BIN_NAME"), subcommand.get_name())))
+        .collect();
+
+    for page in std::iter::once(command).chain(subcommands) {
+        let path = output_dir.join(format!("{}.1", page.get_name()));
+        let mut file = fs::File::create(&path)?;
+        Man::new(page).render(&mut file)?;
+        log::debug!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use miniparse::merge::{MergeStrategy, merge};
+
+use crate::dialect;
+use crate::input::read_ini_file;
+use crate::output::write_output;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Strategy {
+    /// Later files override the values of earlier files
+    Override,
+    /// The first value that was found is kept
+    KeepFirst,
+    /// Fail when files disagree on a value
+    Error,
+}
+
+impl From<Strategy> for MergeStrategy {
+    fn from(strategy: Strategy) -> Self {
+        match strategy {
+            Strategy::Override => MergeStrategy::Override,
+            Strategy::KeepFirst => MergeStrategy::KeepBase,
+            Strategy::Error => MergeStrategy::Error,
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct MergeArgs {
+    /// File paths of the .ini files, from lowest to highest precedence
+    #[arg(required = true, num_args = 2..)]
+    paths: Vec<PathBuf>,
+
+    /// File path to write the merged file to. Printed to stdout when left empty.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// How to resolve keys that have different values in different files
+    #[arg(long, value_enum, default_value_t = Strategy::Override)]
+    strategy: Strategy,
+}
+
+pub fn run(args: MergeArgs) -> anyhow::Result<()> {
+    let contents = args.paths.iter().map(|path| read_ini_file(path)).collect::<Result<Vec<_>, _>>()?;
+
+    let mut merged = dialect::parse(&contents[0])?;
+    for overlay_contents in &contents[1..] {
+        merged = merge(merged, dialect::parse(overlay_contents)?, args.strategy.into())?;
+    }
+
+    write_output(args.output.as_deref(), &merged.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+pub mod apply;
+pub mod assert;
+pub mod browse;
+pub mod cat;
+pub mod completions;
+pub mod convert;
+pub mod count;
+pub mod delete;
+pub mod diff;
+pub mod dump;
+pub mod edit;
+pub mod exists;
+pub mod export;
+pub mod fmt;
+pub mod get;
+pub mod grep;
+pub mod json_schema;
+pub mod lint;
+pub mod list_keys;
+pub mod list_sections;
+pub mod man;
+pub mod merge;
+pub mod query;
+pub mod rename;
+pub mod repair;
+pub mod resolve;
+pub mod set;
+pub mod sort;
+pub mod stats;
+pub mod template;
+pub mod tree;
+pub mod validate;
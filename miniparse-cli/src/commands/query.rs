@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::query::{Query, QueryMatch};
+use serde_json::json;
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::IniSource;
+use crate::output::{FoundValue, ReportFormat};
+
+#[derive(Args, Debug)]
+pub struct QueryArgs {
+    /// Query like `section.key`, with `*` and `?` wildcards and an optional value filter (`=pattern`, `!=pattern` or
+    /// `~regex`). See the README for details.
+    query: String,
+
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Match section names and keys regardless of their case
+    #[arg(short, long)]
+    ignore_case: bool,
+
+    /// Output format of the matches [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<ReportFormat>,
+}
+
+fn path_of(found: &QueryMatch<'_>) -> String {
+    FoundValue {
+        section: found.section,
+        key: found.key,
+        value: found.value,
+    }
+    .path()
+}
+
+pub fn run(args: QueryArgs) -> anyhow::Result<()> {
+    let query = if args.ignore_case {
+        Query::parse_ignore_case(&args.query)?
+    } else {
+        Query::parse(&args.query)?
+    };
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let matches = query.run(&ini_file);
+    if matches.is_empty() {
+        return Err(CliError::KeyNotFound(args.query).into());
+    }
+
+    match format_or_default(args.format, ReportFormat::Text) {
+        ReportFormat::Text => {
+            for found in &matches {
+                println!("{}={}", path_of(found), found.value);
+            }
+        }
+        ReportFormat::Json => {
+            let matches: Vec<_> = matches
+                .iter()
+                .map(|found| json!({ "section": found.section, "key": found.key, "value": found.value }))
+                .collect();
+            println!("{}", serde_json::Value::from(matches));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use clap::Args;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct RenameArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section name. Without --key, the section itself is renamed. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Key name
+    #[arg(short, long)]
+    key: Option<String>,
+
+    /// New name of the key or section
+    #[arg(short, long)]
+    to: String,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+pub fn run(args: RenameArgs) -> anyhow::Result<()> {
+    dialect::require_ini("rename")?;
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+
+    let edited = match (&args.key, &args.section) {
+        (Some(key), section) => miniparse::edit::rename_key(&contents, section.as_deref(), key, &args.to)?,
+        (None, Some(section)) => miniparse::edit::rename_section(&contents, section, &args.to)?,
+        (None, None) => return Err(anyhow!("Specify a --key, a --section, or both")),
+    };
+
+    match (edited, args.key, args.section) {
+        (Some(edited), _, _) => source.write(&contents, &edited, &args.write)?,
+        (None, Some(key), _) => return Err(CliError::KeyNotFound(key).into()),
+        (None, None, Some(section)) => return Err(CliError::SectionNotFound(section).into()),
+        (None, None, None) => unreachable!("Either a key or a section is required"),
+    }
+
+    Ok(())
+}
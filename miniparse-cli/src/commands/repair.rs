@@ -0,0 +1,35 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::dialect;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct RepairArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+pub fn run(args: RepairArgs) -> anyhow::Result<()> {
+    dialect::require_ini("repair")?;
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+
+    // The file is likely not parsable yet, so it is not checked with --strict
+    let repaired = miniparse::repair(&contents);
+
+    // Printed to stderr, since the repaired file goes to stdout when reading from stdin
+    for fix in &repaired.fixes {
+        eprintln!("{}:{}: {fix}", source.name(), fix.line);
+    }
+
+    source.write(&contents, &repaired.text, &args.write)?;
+
+    Ok(())
+}
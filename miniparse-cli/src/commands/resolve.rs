@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::merge::Layers;
+use serde_json::json;
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::read_ini_file;
+use crate::output::{FoundValue, ReportFormat};
+
+#[derive(Args, Debug)]
+pub struct ResolveArgs {
+    /// File paths of the .ini files, from lowest to highest precedence
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Section name. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Key name
+    #[arg(short, long)]
+    key: String,
+
+    /// Output format of the resolved value [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<ReportFormat>,
+}
+
+pub fn run(args: ResolveArgs) -> anyhow::Result<()> {
+    let contents = args.paths.iter().map(|path| read_ini_file(path)).collect::<Result<Vec<_>, _>>()?;
+
+    let mut layers = Layers::new();
+    for (path, contents) in args.paths.iter().zip(&contents) {
+        layers.push(path.display().to_string(), dialect::parse(contents)?);
+    }
+
+    let path = FoundValue {
+        section: args.section.as_deref(),
+        key: &args.key,
+        value: "",
+    }
+    .path();
+    let provenance = layers
+        .resolve(args.section.as_deref(), &args.key)
+        .ok_or_else(|| CliError::KeyNotFound(path.clone()))?;
+
+    match format_or_default(args.format, ReportFormat::Text) {
+        ReportFormat::Text => {
+            println!("{path}={} ({})", provenance.value, provenance.source);
+            for (source, value) in &provenance.overridden {
+                println!("  overrides {value} ({source})");
+            }
+        }
+        ReportFormat::Json => {
+            let overridden: Vec<_> = provenance
+                .overridden
+                .iter()
+                .map(|(source, value)| json!({ "source": source, "value": value }))
+                .collect();
+            let resolved = json!({
+                "section": args.section,
+                "key": args.key,
+                "value": provenance.value,
+                "source": provenance.source,
+                "overridden": overridden,
+            });
+            println!("{resolved}");
+        }
+    }
+
+    Ok(())
+}
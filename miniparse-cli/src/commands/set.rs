@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::address::resolve_section;
+
+use crate::dialect;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct SetArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Section name, or dotted path like `remote.origin` for `[remote "origin"]`. Leave empty for global section.
+    #[arg(short, long)]
+    section: Option<String>,
+
+    /// Key name
+    #[arg(short, long)]
+    key: String,
+
+    /// New value
+    #[arg(short, long)]
+    value: String,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+pub fn run(args: SetArgs) -> anyhow::Result<()> {
+    dialect::require_ini("set")?;
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+
+    let section = args.section.as_deref().map(|section| resolve_section(&contents, section)).transpose()?;
+    let edited = miniparse::edit::set(&contents, section, &args.key, &args.value)?;
+
+    source.write(&contents, &edited, &args.write)?;
+
+    Ok(())
+}
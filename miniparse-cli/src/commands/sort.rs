@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::dialect;
+use crate::input::{IniSource, WriteArgs};
+
+#[derive(Args, Debug)]
+pub struct SortArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Sort the sections by name. When neither --sections nor --keys is given, both are sorted.
+    #[arg(long)]
+    sections: bool,
+
+    /// Sort the keys within every section. When neither --sections nor --keys is given, both are sorted.
+    #[arg(long)]
+    keys: bool,
+
+    #[command(flatten)]
+    write: WriteArgs,
+}
+
+pub fn run(args: SortArgs) -> anyhow::Result<()> {
+    dialect::require_ini("sort")?;
+
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    dialect::check(&contents)?;
+    let mut sorted = contents.clone();
+
+    let sort_both = !args.sections && !args.keys;
+
+    if args.sections || sort_both {
+        sorted = miniparse::sort::sort_sections(&sorted)?;
+    }
+    if args.keys || sort_both {
+        sorted = miniparse::sort::sort_keys(&sorted)?;
+    }
+
+    source.write(&contents, &sorted, &args.write)?;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use anyhow::anyhow;
+use clap::Args;
+use miniparse::models::IniSection;
+use serde_json::json;
+
+use crate::config::format_or_default;
+use crate::dialect;
+use crate::input::IniSource;
+use crate::output::{FoundValue, ReportFormat};
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Number of longest values to list
+    #[arg(long, default_value_t = 3)]
+    longest: usize,
+
+    /// Output format of the statistics [default: text]
+    #[arg(short, long, value_enum)]
+    format: Option<ReportFormat>,
+}
+
+/// Detects the encoding from the byte order mark, and decodes the contents.
+fn decode(bytes: &[u8]) -> anyhow::Result<(&'static str, String)> {
+    let decode_utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| from_bytes([pair[0], pair[1]])).collect();
+        String::from_utf16(&units).map_err(|_| anyhow!("The file is not valid UTF-16"))
+    };
+
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => Ok(("UTF-8 with BOM", String::from_utf8(rest.to_vec())?)),
+        [0xFF, 0xFE, rest @ ..] => Ok(("UTF-16LE", decode_utf16(rest, u16::from_le_bytes)?)),
+        [0xFE, 0xFF, rest @ ..] => Ok(("UTF-16BE", decode_utf16(rest, u16::from_be_bytes)?)),
+        _ if bytes.is_ascii() => Ok(("ASCII", String::from_utf8(bytes.to_vec())?)),
+        _ => match String::from_utf8(bytes.to_vec()) {
+            Ok(contents) => Ok(("UTF-8", contents)),
+            Err(_) => Ok(("unknown (not UTF-8)", String::from_utf8_lossy(bytes).into_owned())),
+        },
+    }
+}
+
+fn line_endings(contents: &str) -> &'static str {
+    let crlf_count = contents.matches("\r\n").count();
+    let lf_count = contents.matches('\n').count() - crlf_count;
+
+    match (crlf_count, lf_count) {
+        (0, 0) => "none",
+        (_, 0) => "CRLF",
+        (0, _) => "LF",
+        _ => "mixed",
+    }
+}
+
+fn duplicate_count(section: &IniSection<'_>) -> usize {
+    section
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(index, entry)| section.entries[..*index].iter().any(|previous| previous.key == entry.key))
+        .count()
+}
+
+/// The count followed by the noun, in plural unless the count is 1
+fn count_of(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {noun}"),
+        _ => format!("{count} {noun}s"),
+    }
+}
+
+pub fn run(args: StatsArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let (encoding, contents) = decode(&source.read_bytes()?)?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let mut sections: Vec<(Option<&str>, &IniSection<'_>)> = Vec::new();
+    if let Some(global_section) = ini_file.get_global_section() {
+        sections.push((None, global_section));
+    }
+    sections.extend(ini_file.sections().map(|(name, section)| (Some(name), section)));
+
+    let key_count: usize = sections.iter().map(|(_, section)| section.entries.len()).sum();
+    let duplicates: usize = sections.iter().map(|(_, section)| duplicate_count(section)).sum();
+
+    let mut values: Vec<FoundValue<'_>> = sections
+        .iter()
+        .flat_map(|(name, section)| {
+            section.entries.iter().map(|entry| FoundValue {
+                section: *name,
+                key: entry.key,
+                value: entry.value,
+            })
+        })
+        .collect();
+    // Only the first occurrence of a duplicate key is used, so the others are not listed
+    let mut seen_paths = HashSet::new();
+    values.retain(|found| seen_paths.insert(found.path()));
+    values.sort_by_key(|found| std::cmp::Reverse(found.value.chars().count()));
+    values.truncate(args.longest);
+
+    match format_or_default(args.format, ReportFormat::Text) {
+        ReportFormat::Text => {
+            println!("File:          {}", source.name());
+            println!("Encoding:      {encoding}");
+            println!("Line endings:  {}", line_endings(&contents));
+            println!("Sections:      {}", ini_file.sections().count());
+            println!("Keys:          {key_count} ({})", count_of(duplicates, "duplicate"));
+
+            println!("Keys per section:");
+            let labels: Vec<String> = sections
+                .iter()
+                .map(|(name, _)| name.map_or("(global)".to_string(), |name| format!("[{name}]")))
+                .collect();
+            let width = labels.iter().map(|label| label.chars().count()).max().unwrap_or_default();
+            for (label, (_, section)) in labels.iter().zip(&sections) {
+                println!(
+                    "  {label:width$}  {} ({})",
+                    section.entries.len(),
+                    count_of(duplicate_count(section), "duplicate")
+                );
+            }
+
+            println!("Longest values:");
+            let width = values.iter().map(|found| found.path().chars().count()).max().unwrap_or_default();
+            for found in &values {
+                println!("  {:width$}  {}", found.path(), count_of(found.value.chars().count(), "character"));
+            }
+        }
+        ReportFormat::Json => {
+            let sections: Vec<_> = sections
+                .iter()
+                .map(|(name, section)| json!({ "name": name, "keys": section.entries.len(), "duplicates": duplicate_count(section) }))
+                .collect();
+            let longest_values: Vec<_> = values
+                .iter()
+                .map(|found| json!({ "section": found.section, "key": found.key, "length": found.value.chars().count() }))
+                .collect();
+
+            let stats = json!({
+                "file": source.name(),
+                "encoding": encoding,
+                "line_endings": line_endings(&contents),
+                "section_count": ini_file.sections().count(),
+                "key_count": key_count,
+                "duplicate_count": duplicates,
+                "sections": sections,
+                "longest_values": longest_values,
+            });
+            println!("{stats}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use std::{fs, path::PathBuf};
+
+use clap::Args;
+use miniparse::template::render;
+
+use crate::dialect;
+use crate::input::IniSource;
+
+#[derive(Args, Debug)]
+pub struct TemplateArgs {
+    /// File path of the template, containing placeholders like {{section.key}}
+    template: PathBuf,
+
+    /// File path of the .ini file with the values. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+}
+
+pub fn run(args: TemplateArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let ini_file = dialect::parse(&contents)?;
+
+    let template = fs::read_to_string(&args.template)?;
+    print!("{}", render(&template, &ini_file)?);
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use anstyle::{AnsiColor, Style};
+use clap::Args;
+use miniparse::models::IniSection;
+
+use crate::dialect;
+use crate::input::IniSource;
+use crate::secrets::MaskArgs;
+
+#[derive(Args, Debug)]
+pub struct TreeArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin.
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Maximum number of characters of a value to show
+    #[arg(long, default_value_t = 40)]
+    max_value_length: usize,
+
+    #[command(flatten)]
+    mask_args: MaskArgs,
+}
+
+const SECTION_STYLE: Style = AnsiColor::Blue.on_default().bold();
+const KEY_STYLE: Style = AnsiColor::Cyan.on_default();
+const COUNT_STYLE: Style = Style::new().dimmed();
+
+fn preview(value: &str, max_length: usize) -> String {
+    if value.chars().count() <= max_length {
+        return value.to_string();
+    }
+
+    let truncated: String = value.chars().take(max_length.saturating_sub(1)).collect();
+    format!("{truncated}…")
+}
+
+fn key_count(section: &IniSection<'_>) -> String {
+    match section.entries.len() {
+        1 => "1 key".to_string(),
+        count => format!("{count} keys"),
+    }
+}
+
+pub fn run(args: TreeArgs) -> anyhow::Result<()> {
+    let source = IniSource::from_path_arg(args.path)?;
+    let contents = source.read()?;
+    let mut ini_file = dialect::parse(&contents)?;
+    args.mask_args.mask(&mut ini_file)?;
+
+    let mut sections: Vec<(String, &IniSection<'_>)> = Vec::new();
+    if let Some(global_section) = ini_file.get_global_section() {
+        sections.push(("(global)".to_string(), global_section));
+    }
+    sections.extend(ini_file.sections().map(|(name, section)| (format!("[{name}]"), section)));
+
+    anstream::println!("{}", source.name());
+
+    for (section_index, (label, section)) in sections.iter().enumerate() {
+        let last_section = section_index == sections.len() - 1;
+        let (branch, indent) = if last_section {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+
+        anstream::println!(
+            "{branch}{SECTION_STYLE}{label}{SECTION_STYLE:#} {COUNT_STYLE}({}){COUNT_STYLE:#}",
+            key_count(section)
+        );
+
+        for (entry_index, entry) in section.entries.iter().enumerate() {
+            let entry_branch = if entry_index == section.entries.len() - 1 {
+                "└── "
+            } else {
+                "├── "
+            };
+            anstream::println!(
+                "{indent}{entry_branch}{KEY_STYLE}{}{KEY_STYLE:#} = {}",
+                entry.key,
+                preview(entry.value, args.max_value_length)
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miniparse::schema::IniSchema;
+
+use crate::dialect;
+use crate::error::CliError;
+use crate::input::{IniSource, find_ini_files, for_each_source, read_ini_file};
+
+#[derive(Args, Debug)]
+pub struct ValidateArgs {
+    /// File path of the .ini file. Use - or leave empty to read from stdin. Can be repeated, or be a glob.
+    #[arg(short, long)]
+    path: Vec<PathBuf>,
+
+    /// Validate every .ini file in this directory and its subdirectories
+    #[arg(short, long)]
+    recursive: Option<PathBuf>,
+
+    /// File path of the schema. See the README for its format.
+    #[arg(long)]
+    schema: PathBuf,
+}
+
+pub fn run(args: ValidateArgs) -> anyhow::Result<()> {
+    dialect::require_ini("validate")?;
+
+    let schema_contents = read_ini_file(&args.schema)?;
+    let schema = IniSchema::parse(&schema_contents)?;
+
+    let mut paths = args.path;
+    if let Some(dir) = &args.recursive {
+        paths.extend(find_ini_files(dir)?);
+    }
+    let sources = IniSource::from_path_args(paths)?;
+
+    let validate = |source: &IniSource, contents: &str| -> anyhow::Result<()> {
+        dialect::check(contents)?;
+        let mut violations = schema.validate(contents)?;
+        // Without a schema every section is expected, so only here can --strict reject sections nobody asked for
+        if dialect::strict() {
+            violations.extend(schema.unknown_sections(contents)?);
+            violations.sort_by_key(|violation| violation.line);
+        }
+
+        // Deprecated keys are warnings, and do not fail the validation
+        for warning in schema.deprecations(contents)? {
+            match warning.line {
+                Some(line) => log::warn!("{}:{line}: {warning}", source.name()),
+                None => log::warn!("{}: {warning}", source.name()),
+            }
+        }
+
+        for violation in &violations {
+            match violation.line {
+                Some(line) => println!("{}:{line}: {violation}", source.name()),
+                None => println!("{}: {violation}", source.name()),
+            }
+        }
+
+        if !violations.is_empty() {
+            return Err(CliError::ValidationFailed(violations.len()).into());
+        }
+
+        Ok(())
+    };
+
+    match sources.as_slice() {
+        [source] => validate(source, &source.read()?),
+        _ => for_each_source(&sources, validate),
+    }
+}
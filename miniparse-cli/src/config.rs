@@ -0,0 +1,92 @@
+//! Defaults read from the user configuration file, for flags that would otherwise be repeated on every invocation.
+//!
+//! The file is an ini file itself, at `$MINIPARSE_CONFIG`, `$XDG_CONFIG_HOME/miniparse/config.ini` or
+//! `~/.config/miniparse/config.ini`. Flags given on the command line always take precedence.
+
+use std::{env, fs, path::PathBuf, sync::OnceLock};
+
+use clap::ValueEnum;
+
+#[derive(Debug, Default)]
+pub struct UserConfig {
+    /// Default for `--verbosity`
+    pub verbosity: Option<String>,
+    /// Default for `--format`, used by every command that supports the given format
+    pub format: Option<String>,
+    /// Default for `--dialect`
+    pub dialect: Option<String>,
+}
+
+static USER_CONFIG: OnceLock<UserConfig> = OnceLock::new();
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("MINIPARSE_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(config_dir) => PathBuf::from(config_dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config_dir.join("miniparse").join("config.ini"))
+}
+
+fn read_user_config() -> anyhow::Result<UserConfig> {
+    let Some(path) = config_path().filter(|path| path.exists()) else {
+        return Ok(UserConfig::default());
+    };
+
+    let contents = fs::read_to_string(&path)?;
+    let ini_file = miniparse::parse(&contents)?;
+    let value_of = |key| {
+        ini_file
+            .get_global_section()
+            .and_then(|section| section.get_value_by_key(key))
+            .map(str::to_string)
+    };
+
+    Ok(UserConfig {
+        verbosity: value_of("verbosity"),
+        format: value_of("format"),
+        dialect: value_of("dialect"),
+    })
+}
+
+/// Reads the user configuration file. Problems with the file are reported, and otherwise ignored.
+pub fn load() -> &'static UserConfig {
+    USER_CONFIG.get_or_init(|| {
+        read_user_config().unwrap_or_else(|error| {
+            eprintln!("Warning: ignoring the user configuration file: {error:#}");
+            UserConfig::default()
+        })
+    })
+}
+
+/// The value of the flag when given, otherwise the default for `key` from the user configuration file when it is valid
+/// for this flag, otherwise `default`. An invalid configured value is reported, and otherwise ignored.
+pub fn or_configured<T: ValueEnum>(value: Option<T>, key: &str, configured: Option<&str>, default: T) -> T {
+    if let Some(value) = value {
+        return value;
+    }
+    let Some(configured) = configured else {
+        return default;
+    };
+
+    T::from_str(configured, true).unwrap_or_else(|_| {
+        let possible_values = T::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|possible_value| possible_value.get_name().to_string());
+        eprintln!(
+            "Warning: ignoring {key}={configured} from the user configuration file, expected one of {}",
+            possible_values.collect::<Vec<_>>().join(", ")
+        );
+        default
+    })
+}
+
+/// The output format to use, for commands with a `--format` flag.
+pub fn format_or_default<T: ValueEnum>(format: Option<T>, default: T) -> T {
+    or_configured(format, "format", load().format.as_deref(), default)
+}
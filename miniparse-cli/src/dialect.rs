@@ -0,0 +1,94 @@
+//! How input files are read, chosen once per invocation: the dialect, set with `--dialect` or the user configuration
+//! file, and whether `--strict` turns skipped lines and duplicates into errors. With `--dialect auto`, the dialect is
+//! guessed from the contents of every file that is read.
+
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+use clap::ValueEnum;
+use miniparse::ParseError;
+use miniparse::dialect::{Dialect, detect_dialect};
+use miniparse::models::IniFile;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DialectArg {
+    /// `key=value` entries without spaces, and `[section]` headers
+    Ini,
+    /// Git configuration, with `[section "subsection"]` headers and quoted values
+    Gitconfig,
+    /// systemd unit files, whose values may contain spaces
+    Systemd,
+    /// Java properties, with `=`, `:` or whitespace delimiters and no sections
+    Properties,
+    /// .env files, with `export` prefixes, quoted values and no sections
+    Dotenv,
+    /// EditorConfig files, with `[glob]` sections
+    Editorconfig,
+    /// Guess the dialect from the contents of each file
+    Auto,
+}
+
+impl DialectArg {
+    /// The dialect preset, or `None` for `auto`
+    fn preset(self) -> Option<Dialect> {
+        match self {
+            Self::Ini => Some(Dialect::Ini),
+            Self::Gitconfig => Some(Dialect::Gitconfig),
+            Self::Systemd => Some(Dialect::Systemd),
+            Self::Properties => Some(Dialect::Properties),
+            Self::Dotenv => Some(Dialect::Dotenv),
+            Self::Editorconfig => Some(Dialect::Editorconfig),
+            Self::Auto => None,
+        }
+    }
+}
+
+static DIALECT: OnceLock<DialectArg> = OnceLock::new();
+static STRICT: OnceLock<bool> = OnceLock::new();
+
+pub fn set_dialect(dialect: DialectArg) {
+    let _ = DIALECT.set(dialect);
+}
+
+/// The dialect to read the contents in: the chosen one, or the best guess with `--dialect auto`.
+pub fn selected(contents: &str) -> Dialect {
+    let dialect = DIALECT.get().copied().unwrap_or(DialectArg::Ini);
+
+    dialect.preset().unwrap_or_else(|| {
+        let guess = detect_dialect(contents);
+        log::debug!("Detected the {} dialect, with confidence {:.2}", guess.dialect, guess.confidence);
+        guess.dialect
+    })
+}
+
+pub fn set_strict(strict: bool) {
+    let _ = STRICT.set(strict);
+}
+
+pub fn strict() -> bool {
+    STRICT.get().copied().unwrap_or_default()
+}
+
+/// Parses the contents in the selected dialect, strictly with `--strict`.
+pub fn parse(contents: &str) -> Result<IniFile<'_>, ParseError> {
+    if strict() {
+        selected(contents).parse_strict(contents)
+    } else {
+        selected(contents).parse(contents)
+    }
+}
+
+/// With `--strict`, fails when the contents have unparsable lines, duplicate keys or sections that are defined twice. For
+/// commands that work on the raw contents instead of parsing them.
+pub fn check(contents: &str) -> Result<(), ParseError> {
+    if strict() { selected(contents).check_strict(contents) } else { Ok(()) }
+}
+
+/// Fails for commands that edit or check the lines of a file, which only understand plain ini. With `--dialect auto`,
+/// these commands read the file as ini.
+pub fn require_ini(command: &str) -> anyhow::Result<()> {
+    match DIALECT.get().copied().unwrap_or(DialectArg::Ini).preset() {
+        Some(Dialect::Ini) | None => Ok(()),
+        Some(dialect) => Err(anyhow!("{command} only supports the ini dialect, not {dialect}")),
+    }
+}
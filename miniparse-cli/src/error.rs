@@ -0,0 +1,134 @@
+use std::{process::ExitCode, sync::OnceLock};
+
+use anstyle::{AnsiColor, Style};
+use clap::ValueEnum;
+use miniparse::ParseError;
+use serde_json::json;
+use thiserror::Error;
+
+/// How errors are printed to stderr
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum ErrorFormat {
+    /// A human readable message
+    #[default]
+    Text,
+    /// A JSON object per error, with the exit code, message, and line and column when known
+    Json,
+}
+
+static ERROR_FORMAT: OnceLock<ErrorFormat> = OnceLock::new();
+
+const ERROR_STYLE: Style = AnsiColor::Red.on_default().bold();
+
+/// Failures that scripts may want to tell apart, each with its own exit code.
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("The key {0} was not found")]
+    KeyNotFound(String),
+    #[error("The section {0} was not found")]
+    SectionNotFound(String),
+    #[error("Found {0} schema violation(s)")]
+    ValidationFailed(usize),
+    #[error("Found {0} denied lint finding(s)")]
+    LintFailed(usize),
+    #[error("{0}")]
+    AssertionFailed(String),
+    #[error("{0} is not formatted")]
+    NotFormatted(String),
+    /// Exits with the code of the first failure
+    #[error("{failed} of {total} files failed")]
+    FilesFailed { failed: usize, total: usize, exit_code: u8 },
+    /// Exits with the code without printing anything, for `exists` and `get --quiet`
+    #[error("Exited with code {0}")]
+    Quiet(u8),
+}
+
+/// Any other error
+pub const EXIT_FAILURE: u8 = 1;
+// 2 is used by clap when the command line arguments are invalid
+pub const EXIT_KEY_NOT_FOUND: u8 = 3;
+pub const EXIT_SECTION_NOT_FOUND: u8 = 4;
+pub const EXIT_PARSE_ERROR: u8 = 5;
+pub const EXIT_IO_ERROR: u8 = 6;
+pub const EXIT_VALIDATION_FAILED: u8 = 7;
+
+pub const EXIT_CODES_HELP: &str = "Exit codes:
+  0  Success
+  1  Any other error
+  2  Invalid arguments
+  3  Key not found
+  4  Section not found
+  5  The .ini file could not be parsed
+  6  The file could not be read or written
+  7  The file does not match the schema, has denied lint findings, or an assertion failed";
+
+pub fn exit_code_for(error: &anyhow::Error) -> ExitCode {
+    ExitCode::from(exit_code_number(error))
+}
+
+pub fn exit_code_number(error: &anyhow::Error) -> u8 {
+    if let Some(cli_error) = error.downcast_ref::<CliError>() {
+        match cli_error {
+            CliError::KeyNotFound(_) => EXIT_KEY_NOT_FOUND,
+            CliError::SectionNotFound(_) => EXIT_SECTION_NOT_FOUND,
+            CliError::ValidationFailed(_) | CliError::LintFailed(_) | CliError::AssertionFailed(_) => EXIT_VALIDATION_FAILED,
+            CliError::NotFormatted(_) => EXIT_FAILURE,
+            CliError::FilesFailed { exit_code, .. } | CliError::Quiet(exit_code) => *exit_code,
+        }
+    } else if let Some(parse_error) = error.downcast_ref::<ParseError>() {
+        match parse_error {
+            ParseError::UnresolvedPlaceholders(_) => EXIT_KEY_NOT_FOUND,
+            ParseError::Io(_) => EXIT_IO_ERROR,
+            ParseError::RegexCaptureGroupNotFound(_)
+            | ParseError::UnknownValueType(_)
+            | ParseError::UnknownLintRule(_)
+            | ParseError::UnknownDialect(_)
+            | ParseError::StrictViolation { .. }
+            | ParseError::InterpolationCycle(_)
+            | ParseError::ConversionFailed(_) => EXIT_PARSE_ERROR,
+            ParseError::InvalidEntry(_)
+            | ParseError::InvalidSectionHeader(_)
+            | ParseError::AlreadyExists(_)
+            | ParseError::MergeConflict(_)
+            | ParseError::MigrationFailed(_)
+            | ParseError::InvalidQuery(_) => EXIT_FAILURE,
+        }
+    } else if error.downcast_ref::<std::io::Error>().is_some() {
+        EXIT_IO_ERROR
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+/// Replaces the error by one that exits with the same code, without printing anything.
+pub fn quiet(error: anyhow::Error) -> anyhow::Error {
+    CliError::Quiet(exit_code_number(&error)).into()
+}
+
+pub fn set_error_format(format: ErrorFormat) {
+    let _ = ERROR_FORMAT.set(format);
+}
+
+/// Prints the error to stderr, in the error format chosen on the command line.
+pub fn print_error(error: &anyhow::Error) {
+    if let Some(CliError::Quiet(_)) = error.downcast_ref::<CliError>() {
+        return;
+    }
+
+    match ERROR_FORMAT.get().copied().unwrap_or_default() {
+        ErrorFormat::Text => anstream::eprintln!("{ERROR_STYLE}Error:{ERROR_STYLE:#} {error:#}"),
+        ErrorFormat::Json => {
+            let (line, column) = match error.downcast_ref::<ParseError>() {
+                Some(ParseError::StrictViolation { line, column, .. }) => (Some(line), Some(column)),
+                _ => (None, None),
+            };
+            let error_json = json!({
+                "code": exit_code_number(error),
+                "message": format!("{error:#}"),
+                "line": line,
+                "column": column,
+            });
+            eprintln!("{error_json}");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Fetching .ini files over HTTP(S), for `--path https://...`. Only available when the CLI is built with the `http`
+//! feature.
+
+use std::{io, path::Path};
+
+#[cfg(feature = "http")]
+use std::{sync::OnceLock, time::Duration};
+
+/// Default for `--http-timeout`
+#[cfg(feature = "http")]
+pub const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+
+/// Default for `--http-max-size`, 10 MiB
+#[cfg(feature = "http")]
+pub const DEFAULT_MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Limits on fetching a file, set with `--http-timeout` and `--http-max-size`
+#[cfg(feature = "http")]
+#[derive(Debug, Clone, Copy)]
+pub struct HttpLimits {
+    pub timeout: Duration,
+    /// In bytes
+    pub max_size: u64,
+}
+
+#[cfg(feature = "http")]
+impl Default for HttpLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECONDS),
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+static LIMITS: OnceLock<HttpLimits> = OnceLock::new();
+
+#[cfg(feature = "http")]
+pub fn set_limits(limits: HttpLimits) {
+    let _ = LIMITS.set(limits);
+}
+
+/// Whether the path is an `http://` or `https://` URL
+pub fn is_url(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|path| path.starts_with("http://") || path.starts_with("https://"))
+}
+
+/// Downloads the file at the URL. Error statuses, timeouts and files over the size limit are reported as errors.
+#[cfg(feature = "http")]
+pub fn fetch(url: &str) -> io::Result<Vec<u8>> {
+    let limits = LIMITS.get().copied().unwrap_or_default();
+    let failed = |err: ureq::Error| io::Error::other(format!("{url}: {err}"));
+
+    let agent = ureq::Agent::config_builder().timeout_global(Some(limits.timeout)).build().new_agent();
+    let mut response = agent.get(url).call().map_err(failed)?;
+    response.body_mut().with_config().limit(limits.max_size).read_to_vec().map_err(failed)
+}
+
+#[cfg(not(feature = "http"))]
+pub fn fetch(url: &str) -> io::Result<Vec<u8>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Cannot fetch {url}, because miniparse was built without the http feature"),
+    ))
+}
@@ -0,0 +1,225 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, anyhow};
+use clap::Args;
+use miniparse::files::{backup, write_atomic};
+use similar::TextDiff;
+use walkdir::WalkDir;
+
+use crate::error::{CliError, exit_code_number, print_error};
+use crate::http;
+
+/// Flags shared by the commands that edit a file
+#[derive(Args, Debug)]
+pub struct WriteArgs {
+    /// Edit the file in place, which is the default. With a suffix, like --in-place=.bak, a copy of the original file is
+    /// kept with the suffix appended to its name.
+    #[arg(short = 'i', long, value_name = "SUFFIX", num_args = 0..=1, require_equals = true, default_missing_value = "")]
+    in_place: Option<String>,
+
+    /// Write the result to this file, instead of editing the original file
+    #[arg(short, long, conflicts_with = "in_place")]
+    output: Option<PathBuf>,
+
+    /// Print a unified diff of the changes instead of writing them
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Where the ini contents of a command come from, and where edits are written back to.
+#[derive(Debug)]
+pub enum IniSource {
+    Stdin,
+    File(PathBuf),
+    /// An `http://` or `https://` URL, which can be read but not written
+    Url(String),
+}
+
+impl IniSource {
+    /// Uses stdin when the path is `-`, or when no path was given and stdin is not a terminal.
+    pub fn from_path_arg(path: Option<PathBuf>) -> anyhow::Result<Self> {
+        match path {
+            Some(path) if path.as_os_str() == "-" => Ok(Self::Stdin),
+            Some(path) if http::is_url(&path) => Ok(Self::Url(path.to_string_lossy().into_owned())),
+            Some(path) => Ok(Self::File(path)),
+            None if !io::stdin().is_terminal() => Ok(Self::Stdin),
+            None => Err(anyhow!("No --path given and nothing was piped to stdin")),
+        }
+    }
+
+    /// Like [`IniSource::from_path_arg`], for commands that accept multiple paths. Paths containing wildcards are
+    /// expanded to the files they match, in alphabetical order.
+    pub fn from_path_args(paths: Vec<PathBuf>) -> anyhow::Result<Vec<Self>> {
+        if paths.is_empty() {
+            return Ok(vec![Self::from_path_arg(None)?]);
+        }
+
+        let mut sources = Vec::new();
+        for path in paths {
+            let pattern = path.to_string_lossy();
+            // URLs may contain a `?`, but are never globs
+            if !pattern.contains(['*', '?', '[']) || http::is_url(&path) {
+                sources.push(Self::from_path_arg(Some(path))?);
+                continue;
+            }
+
+            let matches = glob::glob(&pattern)?.collect::<Result<Vec<_>, _>>()?;
+            if matches.is_empty() {
+                return Err(anyhow!("No files match {pattern}"));
+            }
+            sources.extend(matches.into_iter().map(Self::File));
+        }
+
+        Ok(sources)
+    }
+
+    /// Name to refer to the source by in output and messages
+    pub fn name(&self) -> String {
+        match self {
+            Self::Stdin => "<stdin>".to_string(),
+            Self::File(path) => path.display().to_string(),
+            Self::Url(url) => url.clone(),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Self::Stdin => read_stdin(),
+            Self::File(path) => read_ini_file(path),
+            Self::Url(url) => read_url(url),
+        }
+    }
+
+    /// Like [`IniSource::read`], without warning about files that are not .ini files.
+    pub fn read_file(&self) -> io::Result<String> {
+        match self {
+            Self::Stdin => read_stdin(),
+            Self::File(path) => fs::read_to_string(path),
+            Self::Url(url) => read_url(url),
+        }
+    }
+
+    /// The raw bytes of the source, for when the encoding is not known to be UTF-8.
+    pub fn read_bytes(&self) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Stdin => {
+                let mut contents = Vec::new();
+                io::stdin().read_to_end(&mut contents)?;
+                Ok(contents)
+            }
+            Self::File(path) => fs::read(path),
+            Self::Url(url) => http::fetch(url),
+        }
+    }
+
+    /// Writes edited contents back to the file, or to stdout when the contents were read from stdin. With `--dry-run`,
+    /// prints the difference with the original contents instead.
+    pub fn write(&self, original: &str, contents: &str, write_args: &WriteArgs) -> anyhow::Result<()> {
+        if write_args.dry_run {
+            let name = self.name();
+            let diff = TextDiff::from_lines(original, contents);
+            print!("{}", diff.unified_diff().header(&name, &name));
+            return Ok(());
+        }
+
+        if let Some(output) = &write_args.output {
+            write_atomic(output, contents)?;
+            return Ok(());
+        }
+
+        match (self, write_args.in_place.as_deref()) {
+            (Self::Stdin, Some(_)) => return Err(anyhow!("--in-place needs a file to edit, and cannot be used with stdin")),
+            (Self::Stdin, None) => io::stdout().write_all(contents.as_bytes())?,
+            (Self::Url(url), _) => return Err(anyhow!("{url} can not be edited in place, pass -o to write the result to a file")),
+            (Self::File(path), backup_suffix) => {
+                if let Some(backup_suffix) = backup_suffix.filter(|backup_suffix| !backup_suffix.is_empty()) {
+                    backup(path, backup_suffix)?;
+                }
+                write_atomic(path, contents)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_url(url: &str) -> io::Result<String> {
+    String::from_utf8(http::fetch(url)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{url}: {err}")))
+}
+
+fn read_stdin() -> io::Result<String> {
+    let mut contents = String::new();
+    io::stdin().read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+/// Finds all .ini files in the directory and its subdirectories, in alphabetical order.
+pub fn find_ini_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry?;
+        if entry.file_type().is_file() && entry.path().extension().is_some_and(|extension| extension == "ini") {
+            paths.push(entry.into_path());
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(anyhow!("No .ini files found in {}", dir.display()));
+    }
+
+    Ok(paths)
+}
+
+/// Reads every source and passes its contents to `run`. A failing source is reported and does not stop the others; when
+/// any failed, the returned error summarizes how many.
+pub fn for_each_source(sources: &[IniSource], mut run: impl FnMut(&IniSource, &str) -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let mut failed = 0;
+    let mut exit_code = None;
+
+    for source in sources {
+        let result = source
+            .read()
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| run(source, &contents))
+            .with_context(|| source.name());
+
+        if let Err(error) = result {
+            print_error(&error);
+            failed += 1;
+            exit_code.get_or_insert(exit_code_number(&error));
+        }
+    }
+
+    match exit_code {
+        Some(exit_code) => Err(CliError::FilesFailed {
+            failed,
+            total: sources.len(),
+            exit_code,
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Looks up an environment variable, for `${ENV_VAR}` references in values.
+pub fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+pub fn read_ini_file(path: &Path) -> io::Result<String> {
+    if let Some(extension) = path.extension() {
+        if extension != "ini" {
+            log::warn!("Specified file does not have an .ini extension!");
+        }
+    } else {
+        log::warn!("Specified file does not have an .ini extension!");
+    };
+
+    // Try to read the file regardless
+    fs::read_to_string(path)
+}
@@ -1,13 +1,55 @@
-use std::{
-    fs::{self},
-    path::PathBuf,
-};
+mod commands;
+mod config;
+mod dialect;
+mod error;
+mod http;
+mod input;
+mod output;
+mod secrets;
+mod value_type;
+mod watch;
 
-use anyhow::anyhow;
-use clap::{Parser, ValueEnum};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
 use env_logger::Builder as LoggerBuilder;
 use log::LevelFilter;
 
+use crate::commands::apply::ApplyArgs;
+use crate::commands::assert::AssertArgs;
+use crate::commands::browse::BrowseArgs;
+use crate::commands::cat::CatArgs;
+use crate::commands::completions::CompletionsArgs;
+use crate::commands::convert::ConvertArgs;
+use crate::commands::count::CountArgs;
+use crate::commands::delete::DeleteArgs;
+use crate::commands::diff::DiffArgs;
+use crate::commands::dump::DumpArgs;
+use crate::commands::edit::EditArgs;
+use crate::commands::exists::ExistsArgs;
+use crate::commands::export::ExportArgs;
+use crate::commands::fmt::FmtArgs;
+use crate::commands::get::GetArgs;
+use crate::commands::grep::GrepArgs;
+use crate::commands::json_schema::JsonSchemaArgs;
+use crate::commands::lint::LintArgs;
+use crate::commands::list_keys::ListKeysArgs;
+use crate::commands::list_sections::ListSectionsArgs;
+use crate::commands::man::ManArgs;
+use crate::commands::merge::MergeArgs;
+use crate::commands::query::QueryArgs;
+use crate::commands::rename::RenameArgs;
+use crate::commands::repair::RepairArgs;
+use crate::commands::resolve::ResolveArgs;
+use crate::commands::set::SetArgs;
+use crate::commands::sort::SortArgs;
+use crate::commands::stats::StatsArgs;
+use crate::commands::template::TemplateArgs;
+use crate::commands::tree::TreeArgs;
+use crate::commands::validate::ValidateArgs;
+use crate::dialect::DialectArg;
+use crate::error::ErrorFormat;
+
 #[derive(Debug, Clone, ValueEnum)]
 enum Verbosity {
     Warnings,
@@ -17,52 +59,172 @@ enum Verbosity {
 
 /// Simple cli tool to introspect .ini files
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, after_help = error::EXIT_CODES_HELP)]
 struct Args {
-    /// File path of the .ini file
-    #[arg(short, long)]
-    path: PathBuf,
+    /// Log level [default: warnings]
+    #[arg(long, global = true, value_enum)]
+    verbosity: Option<Verbosity>,
+
+    /// How errors are printed to stderr
+    #[arg(long, global = true, value_enum, default_value_t = ErrorFormat::Text)]
+    error_format: ErrorFormat,
+
+    /// The INI-like format of the input files [default: ini]
+    #[arg(long, global = true, value_enum)]
+    dialect: Option<DialectArg>,
 
-    /// Section name. Leave empty for global section.
-    #[arg(short, long)]
-    section: Option<String>,
+    /// Fail on unparsable lines, duplicate keys and sections that are defined twice, instead of skipping them. With
+    /// validate, also fail on sections that are not in the schema.
+    #[arg(long, global = true)]
+    strict: bool,
 
-    /// Key name
-    #[arg(short, long)]
-    key: String,
+    /// Seconds to wait for a file passed as an http(s):// URL
+    #[cfg(feature = "http")]
+    #[arg(long, global = true, value_name = "SECONDS", default_value_t = http::DEFAULT_TIMEOUT_SECONDS)]
+    http_timeout: u64,
 
-    /// Silent mode
-    #[arg(value_enum, default_value_t = Verbosity::Warnings)]
-    verbosity: Verbosity,
+    /// Largest file in bytes to download when passed as an http(s):// URL
+    #[cfg(feature = "http")]
+    #[arg(long, global = true, value_name = "BYTES", default_value_t = http::DEFAULT_MAX_SIZE)]
+    http_max_size: u64,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the value of a key
+    Get(GetArgs),
+    /// Check whether a key or section exists, printing nothing and reporting through the exit code
+    Exists(ExistsArgs),
+    /// Print the names of the sections
+    ListSections(ListSectionsArgs),
+    /// Print the keys of a section
+    ListKeys(ListKeysArgs),
+    /// Print the number of sections, or the number of keys in a section
+    Count(CountArgs),
+    /// Print all keys matching a query with wildcards and value filters
+    Query(QueryArgs),
+    /// Search keys and values with a regular expression
+    Grep(GrepArgs),
+    /// Print the file as it was parsed
+    Dump(DumpArgs),
+    /// Print the file with syntax highlighting
+    Cat(CatArgs),
+    /// Print statistics about the sections, keys and encoding of the file
+    Stats(StatsArgs),
+    /// Print the sections and keys as a tree
+    Tree(TreeArgs),
+    /// Set the value of a key, creating the key and section if needed
+    Set(SetArgs),
+    /// Delete a key, or a whole section
+    Delete(DeleteArgs),
+    /// Rename a key, or a whole section
+    Rename(RenameArgs),
+    /// Apply a script of set, delete and rename operations, writing the file once
+    Apply(ApplyArgs),
+    /// Edit the file in $EDITOR, saving it only once it parses and matches the schema
+    Edit(EditArgs),
+    /// Check the file against a schema of required keys and value types
+    Validate(ValidateArgs),
+    /// Convert a schema to a JSON Schema, or a JSON Schema back to a schema
+    JsonSchema(JsonSchemaArgs),
+    /// Check that a key has an expected value, for smoke tests in pipelines
+    Assert(AssertArgs),
+    /// Check the file for likely mistakes, like duplicate keys and unparsable lines
+    Lint(LintArgs),
+    /// Fix common syntax mistakes, like section headers missing their `]` and `==` delimiters
+    Repair(RepairArgs),
+    /// Show the keys that were added, removed or changed between two files
+    Diff(DiffArgs),
+    /// Merge multiple files into one, later files taking precedence
+    Merge(MergeArgs),
+    /// Print the effective value of a key across layered files, and which file supplied it
+    Resolve(ResolveArgs),
+    /// Rewrite the file with consistent spacing and blank lines
+    Fmt(FmtArgs),
+    /// Sort sections and keys alphabetically, keeping comments attached
+    Sort(SortArgs),
+    /// Convert between .ini and other configuration formats
+    Convert(ConvertArgs),
+    /// Print the keys of a section as shell export statements
+    Export(ExportArgs),
+    /// Fill in {{section.key}} placeholders in a template with values from the file
+    Template(TemplateArgs),
+    /// Explore the file in an interactive terminal UI
+    Browse(BrowseArgs),
+    /// Print a completion script for your shell
+    Completions(CompletionsArgs),
+    /// Print the man page, or write the man pages of all commands to a directory
+    Man(ManArgs),
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> ExitCode {
     let args = Args::parse();
+    error::set_error_format(args.error_format);
+
+    let user_config = config::load();
 
-    match args.verbosity {
+    match config::or_configured(args.verbosity, "verbosity", user_config.verbosity.as_deref(), Verbosity::Warnings) {
         Verbosity::Silent => (),
         Verbosity::Warnings => LoggerBuilder::new().filter(None, LevelFilter::Warn).init(),
         Verbosity::Debug => LoggerBuilder::new().filter(None, LevelFilter::Debug).init(),
     }
 
-    if let Some(extension) = args.path.extension() {
-        if extension != "ini" {
-            log::warn!("Specified file does not have an .ini extension!");
-        }
-    } else {
-        log::warn!("Specified file does not have an .ini extension!");
-    };
-
-    // Try to read the file regardless
-
-    let contents = fs::read_to_string(args.path)?;
+    dialect::set_dialect(config::or_configured(
+        args.dialect,
+        "dialect",
+        user_config.dialect.as_deref(),
+        DialectArg::Ini,
+    ));
+    dialect::set_strict(args.strict);
+    #[cfg(feature = "http")]
+    http::set_limits(http::HttpLimits {
+        timeout: std::time::Duration::from_secs(args.http_timeout),
+        max_size: args.http_max_size,
+    });
 
-    let found = miniparse::find(&contents, &args.key, args.section.as_deref())?;
+    let result = match args.command {
+        Command::Get(get_args) => commands::get::run(get_args),
+        Command::Exists(exists_args) => commands::exists::run(exists_args),
+        Command::ListSections(list_sections_args) => commands::list_sections::run(list_sections_args),
+        Command::ListKeys(list_keys_args) => commands::list_keys::run(list_keys_args),
+        Command::Count(count_args) => commands::count::run(count_args),
+        Command::Query(query_args) => commands::query::run(query_args),
+        Command::Grep(grep_args) => commands::grep::run(grep_args),
+        Command::Dump(dump_args) => commands::dump::run(dump_args),
+        Command::Cat(cat_args) => commands::cat::run(cat_args),
+        Command::Stats(stats_args) => commands::stats::run(stats_args),
+        Command::Tree(tree_args) => commands::tree::run(tree_args),
+        Command::Set(set_args) => commands::set::run(set_args),
+        Command::Delete(delete_args) => commands::delete::run(delete_args),
+        Command::Rename(rename_args) => commands::rename::run(rename_args),
+        Command::Apply(apply_args) => commands::apply::run(apply_args),
+        Command::Edit(edit_args) => commands::edit::run(edit_args),
+        Command::Validate(validate_args) => commands::validate::run(validate_args),
+        Command::JsonSchema(json_schema_args) => commands::json_schema::run(json_schema_args),
+        Command::Assert(assert_args) => commands::assert::run(assert_args),
+        Command::Lint(lint_args) => commands::lint::run(lint_args),
+        Command::Repair(repair_args) => commands::repair::run(repair_args),
+        Command::Diff(diff_args) => commands::diff::run(diff_args),
+        Command::Merge(merge_args) => commands::merge::run(merge_args),
+        Command::Resolve(resolve_args) => commands::resolve::run(resolve_args),
+        Command::Fmt(fmt_args) => commands::fmt::run(fmt_args),
+        Command::Sort(sort_args) => commands::sort::run(sort_args),
+        Command::Convert(convert_args) => commands::convert::run(convert_args),
+        Command::Export(export_args) => commands::export::run(export_args),
+        Command::Template(template_args) => commands::template::run(template_args),
+        Command::Browse(browse_args) => commands::browse::run(browse_args),
+        Command::Completions(completions_args) => commands::completions::run(completions_args),
+        Command::Man(man_args) => commands::man::run(man_args),
+    };
 
-    match found {
-        Some(value) => print!("{value}"),
-        None => return Err(anyhow!("The given section did not contain the specified key"))?,
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            error::print_error(&error);
+            error::exit_code_for(&error)
+        }
     }
-
-    Ok(())
 }
@@ -0,0 +1,156 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use clap::ValueEnum;
+use miniparse::convert::shell_quote;
+use miniparse::files::write_atomic;
+use serde_json::{Map, Value, json};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    /// The bare value. Without trailing newline when a single value is printed.
+    Raw,
+    /// A JSON object containing the section, key and value, or an object of `section.key` paths to values
+    Json,
+    /// A shell variable assignment with a single-quoted value
+    Shell,
+    /// A KEY=value line, as found in .env files
+    Env,
+}
+
+/// Format of commands that print a report rather than values
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    /// Human readable text
+    Text,
+    /// Machine readable JSON
+    Json,
+}
+
+/// Format of commands that print a list of names
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ListFormat {
+    /// One name per line
+    Text,
+    /// A JSON array of the names
+    Json,
+    /// A JSON object of the names to their values
+    JsonObject,
+}
+
+#[derive(Debug)]
+pub struct FoundValue<'a> {
+    pub section: Option<&'a str>,
+    pub key: &'a str,
+    pub value: &'a str,
+}
+
+impl FoundValue<'_> {
+    /// `section.key`, or just `key` for the global section.
+    pub fn path(&self) -> String {
+        match self.section {
+            Some(section) => format!("{section}.{}", self.key),
+            None => self.key.to_string(),
+        }
+    }
+}
+
+pub fn format_value(format: OutputFormat, found: &FoundValue<'_>) -> String {
+    let FoundValue { section, key, value } = found;
+
+    match format {
+        OutputFormat::Raw => value.to_string(),
+        OutputFormat::Json => format!("{}\n", json!({ "section": section, "key": key, "value": value })),
+        OutputFormat::Shell => format!("{}={}\n", to_variable_name(key), shell_quote(value)),
+        OutputFormat::Env => format!("{}={value}\n", to_variable_name(key).to_uppercase()),
+    }
+}
+
+/// An object of `section.key` paths to values. Paths found more than once get an array of all their values.
+fn values_object(values: &[FoundValue<'_>]) -> Map<String, Value> {
+    let mut object = Map::new();
+
+    for found in values {
+        match object.get_mut(&found.path()) {
+            Some(Value::Array(existing_values)) => existing_values.push(Value::from(found.value)),
+            Some(existing_value) => *existing_value = Value::Array(vec![existing_value.take(), Value::from(found.value)]),
+            None => {
+                object.insert(found.path(), Value::from(found.value));
+            }
+        }
+    }
+
+    object
+}
+
+/// An object of keys to values, for the entries of a single section. Repeated keys get an array of all their values.
+pub fn entries_object(entries: &[miniparse::models::IniEntry<'_>]) -> Map<String, Value> {
+    let found_values: Vec<FoundValue<'_>> = entries
+        .iter()
+        .map(|entry| FoundValue {
+            section: None,
+            key: entry.key,
+            value: entry.value,
+        })
+        .collect();
+    values_object(&found_values)
+}
+
+/// Formats the names one per line, or as a JSON array.
+pub fn format_names(format: ListFormat, names: &[&str]) -> String {
+    match format {
+        ListFormat::Text => names.iter().map(|name| format!("{name}\n")).collect(),
+        ListFormat::Json | ListFormat::JsonObject => format!("{}\n", json!(names)),
+    }
+}
+
+/// Formats one line per value, or a single JSON object when multiple values are printed as JSON.
+pub fn format_values(format: OutputFormat, values: &[FoundValue<'_>]) -> String {
+    match (format, values) {
+        (_, [single]) => format_value(format, single),
+        (OutputFormat::Json, _) => format!("{}\n", Value::Object(values_object(values))),
+        (OutputFormat::Raw, _) => values.iter().map(|found| format!("{}\n", found.value)).collect(),
+        _ => values.iter().map(|found| format_value(format, found)).collect(),
+    }
+}
+
+/// Formats the values found in multiple files. Every line is prefixed with the name of its file, and JSON output becomes an
+/// object of file names to `section.key` paths to values.
+pub fn format_values_per_file(format: OutputFormat, values_per_file: &[(&str, Vec<FoundValue<'_>>)]) -> String {
+    if let OutputFormat::Json = format {
+        let object: Map<String, Value> = values_per_file
+            .iter()
+            .map(|(name, values)| (name.to_string(), Value::Object(values_object(values))))
+            .collect();
+        return format!("{}\n", Value::Object(object));
+    }
+
+    values_per_file
+        .iter()
+        .flat_map(|(name, values)| values.iter().map(move |found| (name, found)))
+        .map(|(name, found)| match format {
+            OutputFormat::Raw => format!("{name}:{}\n", found.value),
+            _ => format!("{name}:{}", format_value(format, found)),
+        })
+        .collect()
+}
+
+/// Replaces every character that is not allowed in a shell variable name with an underscore.
+pub fn to_variable_name(key: &str) -> String {
+    let name: String = key.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect();
+
+    match name.chars().next() {
+        Some(first) if first.is_ascii_digit() => format!("_{name}"),
+        _ => name,
+    }
+}
+
+/// Writes the contents to the output file atomically, or prints them to stdout when no output file was given.
+pub fn write_output(output: Option<&Path>, contents: &str) -> io::Result<()> {
+    match output {
+        Some(output) => write_atomic(output, contents),
+        None => io::stdout().write_all(contents.as_bytes()),
+    }
+}
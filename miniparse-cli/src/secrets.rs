@@ -0,0 +1,99 @@
+use clap::Args;
+use glob::{MatchOptions, Pattern};
+use miniparse::models::IniFile;
+
+/// Key names that are masked when no patterns are passed to `--mask-secrets`
+const DEFAULT_SECRET_PATTERNS: &str = "*password*,*passwd*,*secret*,*token*,*api_key*,*apikey*";
+
+/// Replacement for the values of secret keys
+pub const MASK: &str = "***";
+
+/// Flags shared by the commands that print values, to hide secrets in their output
+#[derive(Args, Debug)]
+pub struct MaskArgs {
+    /// Replace the values of keys that look like secrets with ***. Optionally takes a comma separated list of key name
+    /// patterns to mask, like --mask-secrets='*password*,*token*'
+    #[arg(long, value_name = "PATTERNS", num_args = 0..=1, require_equals = true, default_missing_value = DEFAULT_SECRET_PATTERNS)]
+    mask_secrets: Option<String>,
+}
+
+impl MaskArgs {
+    /// The key name patterns to mask. Matches nothing without `--mask-secrets`.
+    pub fn secret_keys(&self) -> anyhow::Result<SecretKeys> {
+        let patterns = self
+            .mask_secrets
+            .iter()
+            .flat_map(|patterns| patterns.split(','))
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(Pattern::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SecretKeys { patterns })
+    }
+
+    /// Masks the values of the secret keys in the file.
+    pub fn mask(&self, ini_file: &mut IniFile<'_>) -> anyhow::Result<()> {
+        self.secret_keys()?.mask(ini_file);
+        Ok(())
+    }
+}
+
+pub struct SecretKeys {
+    patterns: Vec<Pattern>,
+}
+
+impl SecretKeys {
+    /// Whether the key matches any of the patterns, regardless of its case.
+    pub fn matches(&self, key: &str) -> bool {
+        let options = MatchOptions {
+            case_sensitive: false,
+            ..MatchOptions::new()
+        };
+        self.patterns.iter().any(|pattern| pattern.matches_with(key, options))
+    }
+
+    pub fn mask(&self, ini_file: &mut IniFile<'_>) {
+        for entry in ini_file.entries_mut() {
+            if self.matches(entry.key) {
+                entry.value = MASK;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use crate::secrets::MaskArgs;
+
+    #[derive(Parser)]
+    struct TestArgs {
+        #[command(flatten)]
+        mask_args: MaskArgs,
+    }
+
+    fn masked(args: &[&str], contents: &str) -> String {
+        let test_args = TestArgs::parse_from(std::iter::once("test").chain(args.iter().copied()));
+        let mut ini_file = miniparse::parse(contents).unwrap();
+        test_args.mask_args.mask(&mut ini_file).unwrap();
+        ini_file.to_string()
+    }
+
+    #[test]
+    fn default_patterns_mask_secret_keys() {
+        let contents = "DB_PASSWORD=x\n[auth]\napi_token=y\nuser=z\n";
+        assert_eq!(
+            masked(&["--mask-secrets"], contents),
+            "DB_PASSWORD = ***\n\n[auth]\napi_token = ***\nuser = z\n"
+        );
+        assert_eq!(masked(&[], contents), "DB_PASSWORD = x\n\n[auth]\napi_token = y\nuser = z\n");
+    }
+
+    #[test]
+    fn custom_patterns_replace_the_defaults() {
+        let contents = "password=x\nuser=z\n";
+        assert_eq!(masked(&["--mask-secrets=user"], contents), "password = x\nuser = ***\n\n");
+    }
+}
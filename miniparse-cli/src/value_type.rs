@@ -0,0 +1,121 @@
+use std::{env, path::PathBuf};
+
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ValueType {
+    /// true/false, yes/no, on/off or 1/0, printed as true or false
+    Bool,
+    /// A whole number
+    Int,
+    /// A decimal number
+    Float,
+    /// A file system path. A leading ~ is expanded to the home directory.
+    Path,
+    /// A duration like 90, 1m30s or 250ms, printed as a number of seconds
+    Duration,
+}
+
+impl ValueType {
+    /// Validates the value and converts it to the normalized representation of this type.
+    pub fn coerce(self, value: &str) -> Result<String, String> {
+        match self {
+            Self::Bool => coerce_bool(value),
+            Self::Int => value.parse::<i64>().map(|int| int.to_string()).map_err(|err| err.to_string()),
+            Self::Float => value.parse::<f64>().map(|float| float.to_string()).map_err(|err| err.to_string()),
+            Self::Path => coerce_path(value),
+            Self::Duration => parse_duration_seconds(value).map(|seconds| seconds.to_string()),
+        }
+    }
+}
+
+fn coerce_bool(value: &str) -> Result<String, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok("true".to_string()),
+        "false" | "no" | "off" | "0" => Ok("false".to_string()),
+        _ => Err("expected one of true/false, yes/no, on/off or 1/0".to_string()),
+    }
+}
+
+fn coerce_path(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err("the path is empty".to_string());
+    }
+
+    let path = match value.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            let home = env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .ok_or("can not expand ~, the home directory is unknown")?;
+            PathBuf::from(home).join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(value),
+    };
+
+    Ok(path.display().to_string())
+}
+
+/// Parses a plain number of seconds, or a sequence of number-unit pairs like `1h30m`.
+fn parse_duration_seconds(value: &str) -> Result<f64, String> {
+    if value.is_empty() {
+        return Err("the duration is empty".to_string());
+    }
+
+    if let Ok(seconds) = value.parse::<f64>() {
+        return Ok(seconds);
+    }
+
+    let mut total_seconds = 0.0;
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let number_length = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let unit_length = rest[number_length..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len() - number_length);
+
+        let (number, unit) = (&rest[..number_length], &rest[number_length..number_length + unit_length]);
+        let number: f64 = number.parse().map_err(|_| format!("expected a number before `{unit}`"))?;
+
+        let unit_seconds = match unit {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            "d" => 86400.0,
+            _ => return Err(format!("unknown unit `{unit}`, expected one of ms, s, m, h or d")),
+        };
+
+        total_seconds += number * unit_seconds;
+        rest = &rest[number_length + unit_length..];
+    }
+
+    Ok(total_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_type::ValueType;
+
+    #[test]
+    fn bool_is_normalized() {
+        assert_eq!(ValueType::Bool.coerce("Yes").unwrap(), "true");
+        assert_eq!(ValueType::Bool.coerce("off").unwrap(), "false");
+        assert!(ValueType::Bool.coerce("maybe").is_err());
+    }
+
+    #[test]
+    fn duration_is_converted_to_seconds() {
+        assert_eq!(ValueType::Duration.coerce("90").unwrap(), "90");
+        assert_eq!(ValueType::Duration.coerce("1h30m").unwrap(), "5400");
+        assert_eq!(ValueType::Duration.coerce("250ms").unwrap(), "0.25");
+        assert!(ValueType::Duration.coerce("5 minutes").is_err());
+        assert!(ValueType::Duration.coerce("").is_err());
+    }
+
+    #[test]
+    fn int_rejects_decimals() {
+        assert_eq!(ValueType::Int.coerce("+042").unwrap(), "42");
+        assert!(ValueType::Int.coerce("4.2").is_err());
+    }
+}
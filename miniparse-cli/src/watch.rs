@@ -0,0 +1,79 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+    sync::mpsc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::anyhow;
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+
+use crate::error::print_error;
+use crate::input::IniSource;
+
+/// How long the files have to be left alone before a change is acted upon, so a burst of writes is handled once
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Calls `run` once, and again every time one of the files changes. Errors are printed rather than returned, so the
+/// files can be fixed while they are being watched. Only returns when the files can no longer be watched.
+pub fn watch(sources: &[IniSource], mut run: impl FnMut() -> anyhow::Result<()>) -> anyhow::Result<()> {
+    let mut paths = Vec::new();
+    for source in sources {
+        match source {
+            IniSource::File(path) => paths.push(path.canonicalize()?),
+            IniSource::Stdin => return Err(anyhow!("--watch needs a file to watch, and cannot be used with stdin")),
+            IniSource::Url(url) => return Err(anyhow!("--watch needs a file to watch, and cannot be used with {url}")),
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, sender)?;
+
+    // Editors often save by replacing the file, which would end a watch on the file itself. Watch the directories
+    // containing the files instead.
+    let mut directories: Vec<&Path> = paths.iter().filter_map(|path| path.parent()).collect();
+    directories.sort();
+    directories.dedup();
+    for directory in directories {
+        debouncer.watcher().watch(directory, RecursiveMode::NonRecursive)?;
+    }
+
+    rerun(&mut run);
+
+    // Reading the files is reported as an event too, so compare the modification times to only act on actual changes
+    let mut modified_times: Vec<_> = paths.iter().map(|path| modified_time(path)).collect();
+
+    for events in receiver {
+        let touched = events?.iter().any(|event| paths.contains(&event.path));
+        if !touched {
+            continue;
+        }
+
+        let new_modified_times: Vec<_> = paths.iter().map(|path| modified_time(path)).collect();
+        if new_modified_times != modified_times {
+            modified_times = new_modified_times;
+            rerun(&mut run);
+        }
+    }
+
+    Ok(())
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn rerun(run: &mut impl FnMut() -> anyhow::Result<()>) {
+    let mut stdout = io::stdout();
+    if stdout.is_terminal() {
+        // Clear the screen, so only the latest output is shown
+        print!("\x1b[2J\x1b[H");
+    }
+
+    if let Err(error) = run() {
+        print_error(&error);
+    }
+
+    let _ = stdout.flush();
+}
@@ -0,0 +1,81 @@
+//! Addressing sections by dotted path.
+//!
+//! Nested sections are commonly written with dotted names like `[database.replica]`, and git-config style files use
+//! quoted subsections like `[remote "origin"]`. Both can be addressed with the dotted path, `database.replica` and
+//! `remote.origin`.
+
+use std::borrow::Cow;
+
+use crate::lines::{LineKind, split_lines};
+use crate::{ParseError, names_equal};
+
+/// The dotted path of a section name. Quoted subsections like `remote "origin"` become `remote.origin`, other names are
+/// returned unchanged.
+pub fn section_path(name: &str) -> Cow<'_, str> {
+    if let Some((section, subsection)) = name.split_once(char::is_whitespace)
+        && let Some(subsection) = subsection.trim().strip_prefix('"').and_then(|subsection| subsection.strip_suffix('"'))
+    {
+        return Cow::Owned(format!("{section}.{subsection}"));
+    }
+
+    Cow::Borrowed(name)
+}
+
+/// The name of the section header in the ini string that `address` refers to. A header with exactly that name takes
+/// precedence over one with that dotted path. When no section matches, `address` is returned as is, so it can be used
+/// to create the section.
+pub fn resolve_section<'a>(ini_string: &'a str, address: &'a str) -> Result<&'a str, ParseError> {
+    resolve_section_matching(ini_string, address, false)
+}
+
+/// Like [`resolve_section`], matching section names and dotted paths regardless of their case.
+pub fn resolve_section_ignore_case<'a>(ini_string: &'a str, address: &'a str) -> Result<&'a str, ParseError> {
+    resolve_section_matching(ini_string, address, true)
+}
+
+fn resolve_section_matching<'a>(ini_string: &'a str, address: &'a str, ignore_case: bool) -> Result<&'a str, ParseError> {
+    let lines = split_lines(ini_string)?;
+    let section_names: Vec<&str> = lines
+        .iter()
+        .filter(|line| matches!(line.kind, LineKind::SectionHeader { .. }))
+        .filter_map(|line| line.section)
+        .collect();
+
+    if let Some(name) = section_names.iter().find(|name| names_equal(name, address, ignore_case)) {
+        return Ok(name);
+    }
+
+    let found = section_names
+        .into_iter()
+        .find(|name| names_equal(&section_path(name), address, ignore_case));
+    Ok(found.unwrap_or(address))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::address::{resolve_section, resolve_section_ignore_case, section_path};
+
+    #[test]
+    fn quoted_subsections_become_dotted_paths() {
+        assert_eq!(section_path(r#"remote "origin""#), "remote.origin");
+        assert_eq!(section_path(r#"branch "feature.x""#), "branch.feature.x");
+        assert_eq!(section_path("database.replica"), "database.replica");
+        assert_eq!(section_path("plain"), "plain");
+    }
+
+    #[test]
+    fn addresses_resolve_to_section_headers() {
+        let ini_string = "[remote \"origin\"]\nurl=x\n[database.replica]\nhost=y\n";
+        assert_eq!(resolve_section(ini_string, "remote.origin").unwrap(), r#"remote "origin""#);
+        assert_eq!(resolve_section(ini_string, "database.replica").unwrap(), "database.replica");
+        assert_eq!(resolve_section(ini_string, "new").unwrap(), "new");
+    }
+
+    #[test]
+    fn addresses_resolve_regardless_of_case_when_asked() {
+        let ini_string = "[remote \"origin\"]\nurl=x\n[Database]\nhost=y\n";
+        assert_eq!(resolve_section_ignore_case(ini_string, "Remote.origin").unwrap(), r#"remote "origin""#);
+        assert_eq!(resolve_section_ignore_case(ini_string, "database").unwrap(), "Database");
+        assert_eq!(resolve_section(ini_string, "Remote.origin").unwrap(), "Remote.origin");
+    }
+}
@@ -10,8 +10,14 @@ impl<'content> IniFileBuilder<'content> {
         Self::default()
     }
 
+    /// Adds the section, unless a section with the same name was added before. Like [`find`](crate::find), the first
+    /// section with a name wins.
     pub fn new_section(mut self, name: &'content str, section: IniSection<'content>) -> Self {
-        self.ini_file.sections.insert(name, section);
+        if self.ini_file.get_section_by_name(name).is_some() {
+            log::warn!("Skipping repeated section [{name}], the first one is used");
+        } else {
+            self.ini_file.sections.push((name, section));
+        }
         self
     }
 
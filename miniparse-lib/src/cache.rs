@@ -0,0 +1,108 @@
+//! Parsing configuration files at most once per change, for applications that consult them on every request.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use crate::ParseError;
+use crate::dialect::Dialect;
+use crate::models::OwnedIniFile;
+
+struct CacheEntry {
+    modified: SystemTime,
+    len: u64,
+    ini_file: Arc<OwnedIniFile>,
+}
+
+/// Parsed files by path. A file is parsed again only when its modification time or size changed since it was last read.
+///
+/// The cache can be shared between threads. Looking up a file that did not change only costs a `stat` call, and files are
+/// read and parsed without holding the lock.
+#[derive(Default)]
+pub struct ConfigCache {
+    dialect: Dialect,
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+}
+
+impl ConfigCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache that parses files in the given dialect.
+    pub fn with_dialect(dialect: Dialect) -> Self {
+        Self { dialect, ..Self::default() }
+    }
+
+    /// The parsed file at `path`, from the cache when the file did not change since it was last parsed.
+    pub fn get(&self, path: impl AsRef<Path>) -> Result<Arc<OwnedIniFile>, ParseError> {
+        let path = path.as_ref();
+        let metadata = fs::metadata(path)?;
+        let modified = metadata.modified()?;
+
+        if let Some(entry) = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(path)
+            && entry.modified == modified
+            && entry.len == metadata.len()
+        {
+            return Ok(Arc::clone(&entry.ini_file));
+        }
+
+        // The lock is not held while reading and parsing, so lookups of other files are not blocked by a slow file. Two
+        // threads may both parse a changed file, in which case the last one to finish is cached.
+        log::debug!("Parsing {}, which changed since it was cached", path.display());
+        let contents = fs::read_to_string(path)?;
+        let ini_file = Arc::new(OwnedIniFile::from(&self.dialect.parse(&contents)?));
+
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(
+            path.to_path_buf(),
+            CacheEntry {
+                modified,
+                len: metadata.len(),
+                ini_file: Arc::clone(&ini_file),
+            },
+        );
+
+        Ok(ini_file)
+    }
+
+    /// Forgets the file, so it is parsed again on the next [`ConfigCache::get`].
+    pub fn invalidate(&self, path: impl AsRef<Path>) {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).remove(path.as_ref());
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Arc};
+
+    use crate::cache::ConfigCache;
+
+    #[test]
+    fn files_are_parsed_again_only_when_changed() {
+        let path = std::env::temp_dir().join(format!("miniparse-cache-{}.ini", std::process::id()));
+        fs::write(&path, "[s]\nk=1\n").unwrap();
+
+        let cache = ConfigCache::new();
+        let first = cache.get(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &cache.get(&path).unwrap()));
+
+        // A different size is noticed even when the modification time did not visibly change
+        fs::write(&path, "[s]\nk=22\n").unwrap();
+        let changed = cache.get(&path).unwrap();
+        assert_eq!(changed.get_section_by_name("s").unwrap().get_value_by_key("k"), Some("22"));
+
+        cache.invalidate(&path);
+        assert!(!Arc::ptr_eq(&changed, &cache.get(&path).unwrap()));
+
+        fs::remove_file(&path).unwrap();
+        assert!(cache.get(&path).is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! Conversion between ini files and other configuration formats.
+//!
+//! Sections map to tables (or objects) and entries to string values. When importing, nested tables become dotted
+//! section names, so `{"database": {"replica": {"host": "..."}}}` becomes a `[database.replica]` section.
+//!
+//! Imports produce ini text rather than an [`IniFile`], since the imported values are not borrowed from an ini string.
+//! Pass the result to [`parse`](crate::parse) to get a model.
+
+use serde_json::{Map, Value};
+
+use crate::ParseError;
+use crate::edit::{validated_entry_line, validated_section_header};
+use crate::models::{IniFile, IniSection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+    Yaml,
+    /// `KEY='value'` lines, as found in .env files. Keys of named sections are prefixed with the section name. Values are
+    /// single-quoted, so the output can be sourced or `eval`'d without the shell expanding anything in them.
+    Env,
+}
+
+fn section_to_table(section: &IniSection<'_>, table: &mut Map<String, Value>) {
+    for entry in section.entries.iter() {
+        // Of repeated keys, the first occurrence wins, matching `IniSection::get_value_by_key`
+        table.entry(entry.key).or_insert_with(|| Value::String(entry.value.to_string()));
+    }
+}
+
+/// Sections become nested tables. A global key with the same name as a section is overwritten by the section.
+fn ini_file_to_value(ini_file: &IniFile<'_>) -> Value {
+    let mut root = Map::new();
+
+    if let Some(global_section) = ini_file.get_global_section() {
+        section_to_table(global_section, &mut root);
+    }
+
+    for (section_name, section) in ini_file.sections.iter() {
+        let mut table = Map::new();
+        section_to_table(section, &mut table);
+        root.insert(section_name.to_string(), Value::Object(table));
+    }
+
+    Value::Object(root)
+}
+
+/// A single quote inside a single-quoted shell string: end the string, add an escaped quote, and start a new string
+const SHELL_QUOTED_QUOTE: &str = r"'\''";
+
+/// Wraps the value in single quotes, so the shell does not expand anything inside of it.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', SHELL_QUOTED_QUOTE))
+}
+
+fn to_env_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+fn ini_file_to_env(ini_file: &IniFile<'_>) -> String {
+    let mut output = String::new();
+
+    let global_entries = ini_file
+        .get_global_section()
+        .into_iter()
+        .flat_map(|section| section.entries.iter().map(|entry| (None, entry)));
+    let section_entries = ini_file
+        .sections
+        .iter()
+        .flat_map(|(section_name, section)| section.entries.iter().map(|entry| (Some(*section_name), entry)));
+
+    for (section_name, entry) in global_entries.chain(section_entries) {
+        let name = match section_name {
+            Some(section_name) => format!("{}_{}", to_env_name(section_name), to_env_name(entry.key)),
+            None => to_env_name(entry.key),
+        };
+        output.push_str(&format!("{name}={}\n", shell_quote(entry.value)));
+    }
+
+    output
+}
+
+/// Converts the ini file into the given format.
+pub fn export(ini_file: &IniFile<'_>, format: Format) -> Result<String, ParseError> {
+    let conversion_failed = |err: &dyn std::fmt::Display| ParseError::ConversionFailed(err.to_string());
+
+    match format {
+        Format::Json => serde_json::to_string_pretty(&ini_file_to_value(ini_file))
+            .map(|json| json + "\n")
+            .map_err(|err| conversion_failed(&err)),
+        Format::Toml => toml::to_string(&ini_file_to_value(ini_file)).map_err(|err| conversion_failed(&err)),
+        Format::Yaml => serde_yaml::to_string(&ini_file_to_value(ini_file)).map_err(|err| conversion_failed(&err)),
+        Format::Env => Ok(ini_file_to_env(ini_file)),
+    }
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+fn write_table(output: &mut String, section_name: Option<&str>, table: &Map<String, Value>) -> Result<(), ParseError> {
+    let has_entries = table.values().any(|value| !value.is_object());
+
+    if let Some(section_name) = section_name
+        && (has_entries || table.is_empty())
+    {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&validated_section_header(section_name)?);
+        output.push('\n');
+    }
+
+    for (key, value) in table.iter().filter(|(_, value)| !value.is_object()) {
+        let value =
+            scalar_to_string(value).ok_or_else(|| ParseError::ConversionFailed(format!("The value of {key} is not a string, number or boolean")))?;
+        output.push_str(&validated_entry_line(key, &value)?);
+        output.push('\n');
+    }
+
+    for (key, value) in table.iter() {
+        if let Value::Object(nested_table) = value {
+            let nested_name = match section_name {
+                Some(section_name) => format!("{section_name}.{key}"),
+                None => key.clone(),
+            };
+            write_table(output, Some(&nested_name), nested_table)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn value_to_ini(value: Value) -> Result<String, ParseError> {
+    let Value::Object(root) = value else {
+        return Err(ParseError::ConversionFailed("The top level must be a table".to_string()));
+    };
+
+    let mut output = String::new();
+    write_table(&mut output, None, &root)?;
+    Ok(output)
+}
+
+fn env_to_ini(input: &str) -> Result<String, ParseError> {
+    let mut output = String::new();
+
+    for line in input.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| ParseError::ConversionFailed(format!("Expected KEY=value, found `{line}`")))?;
+
+        let value = value.trim();
+        let value = match value.strip_prefix('\'').and_then(|value| value.strip_suffix('\'')) {
+            Some(value) => value.replace(SHELL_QUOTED_QUOTE, "'"),
+            None => value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value)
+                .to_string(),
+        };
+
+        output.push_str(&validated_entry_line(key.trim(), &value)?);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// Converts input in the given format into ini text.
+pub fn import(input: &str, format: Format) -> Result<String, ParseError> {
+    let conversion_failed = |err: &dyn std::fmt::Display| ParseError::ConversionFailed(err.to_string());
+
+    match format {
+        Format::Json => value_to_ini(serde_json::from_str(input).map_err(|err| conversion_failed(&err))?),
+        Format::Toml => value_to_ini(toml::from_str(input).map_err(|err| conversion_failed(&err))?),
+        Format::Yaml => value_to_ini(serde_yaml::from_str(input).map_err(|err| conversion_failed(&err))?),
+        Format::Env => env_to_ini(input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::convert::{Format, export, import};
+    use crate::parse;
+
+    const INI: &str = "name = demo\n\n[server]\nhost = localhost\nport = 80\n";
+
+    #[test]
+    fn export_to_json() {
+        let json = export(&parse(INI).unwrap(), Format::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["server"]["port"], "80");
+    }
+
+    #[test]
+    fn export_to_env_prefixes_section() {
+        let env = export(&parse(INI).unwrap(), Format::Env).unwrap();
+        assert_eq!(env, "NAME='demo'\nSERVER_HOST='localhost'\nSERVER_PORT='80'\n");
+    }
+
+    #[test]
+    fn export_to_env_quotes_values_for_the_shell() {
+        let ini_file = parse("price=$5`id`#1\nquote=it's\"\n").unwrap();
+        let env = export(&ini_file, Format::Env).unwrap();
+        assert_eq!(env, "PRICE='$5`id`#1'\nQUOTE='it'\\''s\"'\n");
+        assert_eq!(import(&env, Format::Env).unwrap(), "PRICE = $5`id`#1\nQUOTE = it's\"\n");
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        for format in [Format::Json, Format::Toml, Format::Yaml] {
+            let exported = export(&parse(INI).unwrap(), format).unwrap();
+            assert_eq!(import(&exported, format).unwrap(), INI, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn nested_tables_become_dotted_sections() {
+        let ini = import(r#"{"database": {"replica": {"port": 5432}}}"#, Format::Json).unwrap();
+        assert_eq!(ini, "[database.replica]\nport = 5432\n");
+    }
+
+    #[test]
+    fn import_env_strips_export_and_quotes() {
+        let ini = import("# comment\nexport HOST=\"localhost\"\nPORT=80\n", Format::Env).unwrap();
+        assert_eq!(ini, "HOST = localhost\nPORT = 80\n");
+    }
+}
@@ -0,0 +1,640 @@
+//! Presets for the INI-like formats found in the wild.
+//!
+//! They differ in how comments start, which delimiters separate keys from values, whether values may contain spaces or
+//! quotes, and whether there are sections at all. A [`Dialect`] is passed to the parse and lookup functions to read a
+//! file the way its own tooling would.
+
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::builders::{IniFileBuilder, IniSectionBuilder};
+use crate::lines::COMMENT_PREFIXES;
+use crate::models::{IniEntry, IniFile, IniSection, SectionId};
+use crate::{ENTRY_KEY_GROUP_NAME, ENTRY_VALUE_GROUP_NAME, KEY_VALUE_REGEX, ParseError, SECTION_HEADER_REGEX, SECTION_NAME_GROUP_NAME};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// `key=value` entries without spaces in keys or values, and `[section]` headers
+    #[default]
+    Ini,
+    /// Git configuration: `[section "subsection"]` headers, quoted values, and keys without a value meaning `true`
+    Gitconfig,
+    /// systemd unit files: `Key=Value` entries whose values may contain spaces, and `#` or `;` comments
+    Systemd,
+    /// Java properties: `key=value`, `key: value` or `key value` entries, `#` or `!` comments, and no sections
+    Properties,
+    /// .env files: `KEY=value` entries with an optional `export` prefix and quoted values, and no sections
+    Dotenv,
+    /// EditorConfig: `[glob]` sections and `key = value` entries whose values may contain spaces
+    Editorconfig,
+}
+
+impl Dialect {
+    pub const ALL: [Dialect; 6] = [
+        Self::Ini,
+        Self::Gitconfig,
+        Self::Systemd,
+        Self::Properties,
+        Self::Dotenv,
+        Self::Editorconfig,
+    ];
+}
+
+impl TryFrom<&str> for Dialect {
+    type Error = ParseError;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|dialect| dialect.to_string() == name)
+            .ok_or_else(|| ParseError::UnknownDialect(name.to_string()))
+    }
+}
+
+impl Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Ini => "ini",
+            Self::Gitconfig => "gitconfig",
+            Self::Systemd => "systemd",
+            Self::Properties => "properties",
+            Self::Dotenv => "dotenv",
+            Self::Editorconfig => "editorconfig",
+        };
+        write!(f, "{name}")
+    }
+}
+
+struct Syntax {
+    /// Lines starting with one of these are comments
+    comment_prefixes: &'static [char],
+    entry: Regex,
+    /// `None` when the dialect has no sections
+    section_header: Option<Regex>,
+    /// Value of an entry that only consists of a key, for dialects whose entry regex makes the value optional
+    bare_key_value: &'static str,
+    /// Whether values wrapped in matching single or double quotes are read without the quotes
+    unquote: bool,
+}
+
+fn regex(pattern: &str) -> Regex {
+    Regex::new(pattern).expect("Invalid regex!")
+}
+
+fn bracketed_section_header() -> Option<Regex> {
+    Some(regex(&format!(r"^\[(?P<{SECTION_NAME_GROUP_NAME}>.+)\]$")))
+}
+
+static INI_SYNTAX: LazyLock<Syntax> = LazyLock::new(|| Syntax {
+    comment_prefixes: &COMMENT_PREFIXES,
+    entry: KEY_VALUE_REGEX.clone(),
+    section_header: Some(SECTION_HEADER_REGEX.clone()),
+    bare_key_value: "",
+    unquote: false,
+});
+
+static GITCONFIG_SYNTAX: LazyLock<Syntax> = LazyLock::new(|| Syntax {
+    comment_prefixes: &['#', ';'],
+    entry: regex(&format!(
+        r"^(?P<{ENTRY_KEY_GROUP_NAME}>[A-Za-z][-A-Za-z0-9]*)\s*(?:=\s*(?P<{ENTRY_VALUE_GROUP_NAME}>.*))?$"
+    )),
+    section_header: bracketed_section_header(),
+    bare_key_value: "true",
+    unquote: true,
+});
+
+static SYSTEMD_SYNTAX: LazyLock<Syntax> = LazyLock::new(|| Syntax {
+    comment_prefixes: &['#', ';'],
+    entry: regex(&format!(
+        r"^(?P<{ENTRY_KEY_GROUP_NAME}>[A-Za-z0-9_.-]+)\s*=\s*(?P<{ENTRY_VALUE_GROUP_NAME}>.*)$"
+    )),
+    section_header: bracketed_section_header(),
+    bare_key_value: "",
+    unquote: false,
+});
+
+static PROPERTIES_SYNTAX: LazyLock<Syntax> = LazyLock::new(|| Syntax {
+    comment_prefixes: &['#', '!'],
+    entry: regex(&format!(
+        r"^(?P<{ENTRY_KEY_GROUP_NAME}>[^=:\s]+)(?:\s*[=:]\s*|\s+|$)(?P<{ENTRY_VALUE_GROUP_NAME}>.*)$"
+    )),
+    section_header: None,
+    bare_key_value: "",
+    unquote: false,
+});
+
+static DOTENV_SYNTAX: LazyLock<Syntax> = LazyLock::new(|| Syntax {
+    comment_prefixes: &['#'],
+    entry: regex(&format!(
+        r"^(?:export\s+)?(?P<{ENTRY_KEY_GROUP_NAME}>[A-Za-z_][A-Za-z0-9_.]*)\s*=\s*(?P<{ENTRY_VALUE_GROUP_NAME}>.*)$"
+    )),
+    section_header: None,
+    bare_key_value: "",
+    unquote: true,
+});
+
+static EDITORCONFIG_SYNTAX: LazyLock<Syntax> = LazyLock::new(|| Syntax {
+    comment_prefixes: &['#', ';'],
+    entry: regex(&format!(
+        r"^(?P<{ENTRY_KEY_GROUP_NAME}>[^=\s\[]+)\s*=\s*(?P<{ENTRY_VALUE_GROUP_NAME}>.*)$"
+    )),
+    section_header: bracketed_section_header(),
+    bare_key_value: "",
+    unquote: false,
+});
+
+/// What a single trimmed line of a file is, according to a dialect.
+#[derive(Debug)]
+pub(crate) enum ParsedLine<'content> {
+    Blank,
+    Comment,
+    SectionHeader(&'content str),
+    Entry(IniEntry<'content>),
+    Unparsable,
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2
+            && let Some(unquoted) = value.strip_prefix(quote).and_then(|value| value.strip_suffix(quote))
+        {
+            return unquoted;
+        }
+    }
+    value
+}
+
+impl Dialect {
+    fn syntax(self) -> &'static Syntax {
+        match self {
+            Self::Ini => &INI_SYNTAX,
+            Self::Gitconfig => &GITCONFIG_SYNTAX,
+            Self::Systemd => &SYSTEMD_SYNTAX,
+            Self::Properties => &PROPERTIES_SYNTAX,
+            Self::Dotenv => &DOTENV_SYNTAX,
+            Self::Editorconfig => &EDITORCONFIG_SYNTAX,
+        }
+    }
+
+    /// Classifies a line that has already been trimmed.
+    pub(crate) fn parse_line(self, line: &str) -> Result<ParsedLine<'_>, ParseError> {
+        let syntax = self.syntax();
+
+        if line.is_empty() {
+            return Ok(ParsedLine::Blank);
+        }
+
+        if line.starts_with(syntax.comment_prefixes) {
+            return Ok(ParsedLine::Comment);
+        }
+
+        if let Some(entry_captures) = syntax.entry.captures(line) {
+            let key = entry_captures
+                .name(ENTRY_KEY_GROUP_NAME)
+                .ok_or(ParseError::RegexCaptureGroupNotFound(ENTRY_KEY_GROUP_NAME))?
+                .as_str();
+            let value = entry_captures
+                .name(ENTRY_VALUE_GROUP_NAME)
+                .map_or(syntax.bare_key_value, |value| value.as_str().trim_end());
+            let value = if syntax.unquote { unquote(value) } else { value };
+
+            return Ok(ParsedLine::Entry(IniEntry { key, value }));
+        }
+
+        if let Some(section_header_captures) = syntax.section_header.as_ref().and_then(|regex| regex.captures(line)) {
+            let section_name = section_header_captures
+                .name(SECTION_NAME_GROUP_NAME)
+                .ok_or(ParseError::RegexCaptureGroupNotFound(SECTION_NAME_GROUP_NAME))?
+                .as_str();
+
+            return Ok(ParsedLine::SectionHeader(section_name));
+        }
+
+        Ok(ParsedLine::Unparsable)
+    }
+
+    /// Like [`parse`](crate::parse), reading the file in this dialect.
+    pub fn parse(self, ini_string: &str) -> Result<IniFile<'_>, ParseError> {
+        let mut ini_file_builder = IniFileBuilder::new();
+        let mut current_section_builder = IniSectionBuilder::new(SectionId::Global);
+
+        for line in ini_string.lines().map(str::trim) {
+            log::debug!("Parsing line: {line}");
+
+            match self.parse_line(line)? {
+                ParsedLine::Blank | ParsedLine::Comment => log::debug!("Line is blank or a comment: skipping"),
+                ParsedLine::Entry(entry) => {
+                    log::debug!("Line matched key-value regex.");
+                    current_section_builder = current_section_builder.add_entry(entry);
+                }
+                ParsedLine::SectionHeader(section_name) => {
+                    log::debug!("Line matched section start regex, adding current section");
+                    ini_file_builder = crate::add_section_to_ini_builder(ini_file_builder, current_section_builder);
+                    current_section_builder = IniSectionBuilder::new(SectionId::Named(section_name));
+                }
+                ParsedLine::Unparsable => log::warn!("Skipping unparsable non-empty line: {line}"),
+            }
+        }
+
+        log::debug!("End of file reached. Adding current section, if we are building one.");
+        ini_file_builder = crate::add_section_to_ini_builder(ini_file_builder, current_section_builder);
+
+        log::debug!("Building ini file");
+        Ok(ini_file_builder.build())
+    }
+
+    /// Like [`parse_strict`](crate::parse_strict), reading the file in this dialect.
+    pub fn parse_strict(self, ini_string: &str) -> Result<IniFile<'_>, ParseError> {
+        self.check_strict(ini_string)?;
+        self.parse(ini_string)
+    }
+
+    /// Fails on the first line that [`Dialect::parse`] would skip or silently resolve: an unparsable line, a key that
+    /// occurs twice in a section, or a section that is defined twice.
+    pub fn check_strict(self, ini_string: &str) -> Result<(), ParseError> {
+        let mut seen_sections = Vec::new();
+        let mut seen_keys = Vec::new();
+
+        for (index, raw_line) in ini_string.lines().enumerate() {
+            let line = raw_line.trim();
+            // Columns count characters, starting at 1
+            let column_of = |text: &str| raw_line[..raw_line.find(text).unwrap_or_default()].chars().count() + 1;
+            let violation = |column, message| ParseError::StrictViolation {
+                line: index + 1,
+                column,
+                message,
+            };
+
+            match self.parse_line(line)? {
+                ParsedLine::Unparsable => return Err(violation(column_of(line), format!("unparsable line `{line}`"))),
+                ParsedLine::SectionHeader(section_name) => {
+                    if seen_sections.contains(&section_name) {
+                        return Err(violation(column_of(line), format!("section [{section_name}] is defined more than once")));
+                    }
+                    seen_sections.push(section_name);
+                    seen_keys.clear();
+                }
+                ParsedLine::Entry(entry) => {
+                    if seen_keys.contains(&entry.key) {
+                        return Err(violation(column_of(entry.key), format!("duplicate key {}", entry.key)));
+                    }
+                    seen_keys.push(entry.key);
+                }
+                ParsedLine::Blank | ParsedLine::Comment => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Like [`parse_section`](crate::parse_section), reading the file in this dialect.
+    pub fn parse_section<'content>(self, ini_string: &'content str, section_name: &str) -> Result<Option<IniSection<'content>>, ParseError> {
+        let mut section: Option<IniSection<'content>> = None;
+
+        for line in ini_string.lines().map(str::trim) {
+            match (self.parse_line(line)?, section.as_mut()) {
+                // The next header ends the section
+                (ParsedLine::SectionHeader(_), Some(_)) => break,
+                (ParsedLine::SectionHeader(new_section_name), None) if new_section_name == section_name => section = Some(IniSection::new()),
+                (ParsedLine::Entry(entry), Some(section)) => section.entries.push(entry),
+                _ => (),
+            }
+        }
+
+        Ok(section)
+    }
+
+    /// Like [`find`](crate::find), reading the file in this dialect.
+    pub fn find<'content>(
+        self,
+        ini_string: &'content str,
+        key_to_find: &str,
+        section_to_find: Option<&str>,
+    ) -> Result<Option<&'content str>, ParseError> {
+        let mut section_found = false;
+
+        for line in ini_string.lines().map(str::trim) {
+            log::debug!("Searching line: {line}");
+
+            match self.parse_line(line)? {
+                ParsedLine::SectionHeader(new_section_name) if section_to_find.is_some() => {
+                    if section_found {
+                        // We found a new section, while already in the section we were trying to search through.
+                        // So the key wasn't present
+                        log::debug!("Searched through the specified section - key not found");
+                        return Ok(None);
+                    }
+
+                    if Some(new_section_name) == section_to_find {
+                        log::debug!("Section header is the specified section - searching for specified key");
+                        section_found = true;
+                    }
+                }
+                // Still looking for the specified section
+                _ if section_to_find.is_some() && !section_found => (),
+                ParsedLine::Entry(entry) if entry.key == key_to_find => return Ok(Some(entry.value)),
+                _ => (),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Like [`find_all`](crate::find_all), reading the file in this dialect.
+    pub fn find_all<'content>(
+        self,
+        ini_string: &'content str,
+        key_to_find: &str,
+        section_to_find: Option<&str>,
+    ) -> Result<Vec<&'content str>, ParseError> {
+        self.find_all_matching(ini_string, key_to_find, section_to_find, false)
+    }
+
+    /// Like [`find_all_ignore_case`](crate::find_all_ignore_case), reading the file in this dialect.
+    pub fn find_all_ignore_case<'content>(
+        self,
+        ini_string: &'content str,
+        key_to_find: &str,
+        section_to_find: Option<&str>,
+    ) -> Result<Vec<&'content str>, ParseError> {
+        self.find_all_matching(ini_string, key_to_find, section_to_find, true)
+    }
+
+    fn find_all_matching<'content>(
+        self,
+        ini_string: &'content str,
+        key_to_find: &str,
+        section_to_find: Option<&str>,
+        ignore_case: bool,
+    ) -> Result<Vec<&'content str>, ParseError> {
+        let mut values = Vec::new();
+        let mut in_section = section_to_find.is_none();
+
+        for line in ini_string.lines().map(str::trim) {
+            match self.parse_line(line)? {
+                ParsedLine::SectionHeader(new_section_name) => {
+                    if let Some(section_to_find_name) = section_to_find {
+                        in_section = crate::names_equal(new_section_name, section_to_find_name, ignore_case);
+                    }
+                }
+                ParsedLine::Entry(entry) if in_section && crate::names_equal(entry.key, key_to_find, ignore_case) => values.push(entry.value),
+                _ => (),
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// A dialect suggested by [`detect_dialect`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DialectGuess {
+    pub dialect: Dialect,
+    /// Between 0 and 1: 0 when another dialect fits the contents just as well, 1 when no other dialect fits at all
+    pub confidence: f64,
+}
+
+static SUBSECTION_HEADER_REGEX: LazyLock<Regex> = LazyLock::new(|| regex(r#"^\[[^\]\s]+\s+"[^"]*"\]$"#));
+static GLOB_HEADER_REGEX: LazyLock<Regex> = LazyLock::new(|| regex(r"^\[[^\]]*[*{][^\]]*\]$"));
+/// A capitalized key directly followed by `=`, like `ExecStart=`
+static CAPITALIZED_KEY_REGEX: LazyLock<Regex> = LazyLock::new(|| regex(r"^[A-Z][A-Za-z0-9]*[a-z][A-Za-z0-9]*="));
+static LOCALIZED_KEY_REGEX: LazyLock<Regex> = LazyLock::new(|| regex(r"^[A-Za-z][A-Za-z0-9-]*\[[a-z]{2,3}(?:_[A-Z]{2})?(?:@[a-z]+)?\]\s*="));
+static COLON_ENTRY_REGEX: LazyLock<Regex> = LazyLock::new(|| regex(r"^[^=:\s\[]+\s*:\s"));
+static UPPER_CASE_KEY_REGEX: LazyLock<Regex> = LazyLock::new(|| regex(r"^(?:export\s+)?[A-Z][A-Z0-9_]*="));
+
+impl Dialect {
+    /// Fraction of the non-blank lines that this dialect can read
+    fn fraction_parsed(self, lines: &[&str]) -> f64 {
+        if lines.is_empty() {
+            return 0.0;
+        }
+
+        let parsed = lines
+            .iter()
+            .filter(|line| !matches!(self.parse_line(line), Ok(ParsedLine::Unparsable) | Err(_)))
+            .count();
+        parsed as f64 / lines.len() as f64
+    }
+}
+
+/// Whether a key occurs twice in a section, like `ExecStart` in systemd units
+fn has_repeated_keys(lines: &[&str]) -> bool {
+    let mut seen_keys = Vec::new();
+
+    for line in lines {
+        match Dialect::Systemd.parse_line(line) {
+            Ok(ParsedLine::SectionHeader(_)) => seen_keys.clear(),
+            Ok(ParsedLine::Entry(entry)) if seen_keys.contains(&entry.key) => return true,
+            Ok(ParsedLine::Entry(entry)) => seen_keys.push(entry.key),
+            _ => (),
+        }
+    }
+
+    false
+}
+
+/// Suggests the dialect the contents are most likely written in.
+///
+/// Every dialect is scored by the fraction of lines it can read, raised by the signals that point to it: subsection
+/// headers and tab indentation for git configuration, `Capitalized=` keys, locale-suffixed and repeated keys for systemd
+/// units, `!` comments and `key: value` entries for Java properties, `export` prefixes and upper case keys for .env files,
+/// and glob headers and `root = true` for EditorConfig. Plain ini wins when it can read every line, and when nothing can
+/// be told apart.
+pub fn detect_dialect(ini_string: &str) -> DialectGuess {
+    let lines = ini_string.lines().map(str::trim).filter(|line| !line.is_empty()).collect::<Vec<_>>();
+    let any_line = |regex: &Regex| lines.iter().any(|line| regex.is_match(line));
+
+    let signals = [
+        (Dialect::Ini, Dialect::Ini.fraction_parsed(&lines) == 1.0, 0.25),
+        (Dialect::Gitconfig, any_line(&SUBSECTION_HEADER_REGEX), 1.0),
+        (Dialect::Gitconfig, ini_string.lines().any(|line| line.starts_with('\t')), 0.5),
+        (Dialect::Systemd, any_line(&CAPITALIZED_KEY_REGEX), 0.5),
+        (Dialect::Systemd, any_line(&LOCALIZED_KEY_REGEX), 0.5),
+        (Dialect::Systemd, has_repeated_keys(&lines), 0.5),
+        (Dialect::Properties, lines.iter().any(|line| line.starts_with('!')), 1.0),
+        (Dialect::Properties, any_line(&COLON_ENTRY_REGEX), 0.5),
+        (Dialect::Dotenv, lines.iter().any(|line| line.starts_with("export ")), 1.0),
+        (Dialect::Dotenv, any_line(&UPPER_CASE_KEY_REGEX), 0.5),
+        (Dialect::Editorconfig, any_line(&GLOB_HEADER_REGEX), 1.0),
+        (Dialect::Editorconfig, lines.iter().any(|line| line.replace(' ', "") == "root=true"), 0.5),
+    ];
+
+    // Signals only count as far as the dialect can read the file, so a single matching line does not win on its own
+    let scores = Dialect::ALL.map(|dialect| {
+        let bonus = signals
+            .iter()
+            .filter(|(signal_dialect, found, _)| *signal_dialect == dialect && *found)
+            .map(|(_, _, weight)| weight)
+            .sum::<f64>();
+        (dialect, dialect.fraction_parsed(&lines) * (1.0 + bonus))
+    });
+
+    // Of equal scores, the first dialect wins, so plain ini is preferred
+    let (best, best_score) = scores
+        .into_iter()
+        .fold((Dialect::Ini, 0.0), |best, score| if score.1 > best.1 { score } else { best });
+    let runner_up_score = scores
+        .iter()
+        .filter(|(dialect, _)| *dialect != best)
+        .map(|(_, score)| *score)
+        .fold(0.0, f64::max);
+
+    DialectGuess {
+        dialect: best,
+        confidence: if best_score > 0.0 {
+            (best_score - runner_up_score) / best_score
+        } else {
+            0.0
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParseError;
+    use crate::dialect::{Dialect, detect_dialect};
+
+    #[test]
+    fn dialect_names_round_trip() {
+        for dialect in Dialect::ALL {
+            assert_eq!(Dialect::try_from(dialect.to_string().as_str()).unwrap(), dialect);
+        }
+        assert!(Dialect::try_from("toml").is_err());
+    }
+
+    #[test]
+    fn gitconfig_reads_quoted_and_bare_values() {
+        let ini_file = Dialect::Gitconfig
+            .parse("# comment\n[remote \"origin\"]\n\turl = git@host:repo.git\n[core]\n\tbare\n\teditor = \"vim -u NONE\"\n")
+            .unwrap();
+
+        assert_eq!(
+            ini_file.get_section_by_name("remote \"origin\"").unwrap().get_value_by_key("url"),
+            Some("git@host:repo.git")
+        );
+        let core = ini_file.get_section_by_name("core").unwrap();
+        assert_eq!(core.get_value_by_key("bare"), Some("true"));
+        assert_eq!(core.get_value_by_key("editor"), Some("vim -u NONE"));
+    }
+
+    #[test]
+    fn systemd_values_may_contain_spaces() {
+        let ini_file = Dialect::Systemd
+            .parse("[Service]\n; comment\nExecStart=/usr/bin/app --port 80\nEnvironment=\n")
+            .unwrap();
+
+        let service = ini_file.get_section_by_name("Service").unwrap();
+        assert_eq!(service.get_value_by_key("ExecStart"), Some("/usr/bin/app --port 80"));
+        assert_eq!(service.get_value_by_key("Environment"), Some(""));
+    }
+
+    #[test]
+    fn properties_accept_every_delimiter() {
+        let ini_file = Dialect::Properties.parse("! comment\na=1\nb: 2\nc 3\n[d]\n").unwrap();
+
+        let global_section = ini_file.get_global_section().unwrap();
+        assert_eq!(global_section.get_value_by_key("a"), Some("1"));
+        assert_eq!(global_section.get_value_by_key("b"), Some("2"));
+        assert_eq!(global_section.get_value_by_key("c"), Some("3"));
+        assert_eq!(ini_file.sections().count(), 0);
+    }
+
+    #[test]
+    fn dotenv_strips_export_and_quotes() {
+        let contents = "# comment\nexport HOST=localhost\nGREETING=\"hello world\"\nNAME='x'\n";
+
+        assert_eq!(Dialect::Dotenv.find(contents, "HOST", None).unwrap(), Some("localhost"));
+        assert_eq!(Dialect::Dotenv.find_all(contents, "GREETING", None).unwrap(), vec!["hello world"]);
+        assert_eq!(Dialect::Dotenv.find_all_ignore_case(contents, "name", None).unwrap(), vec!["x"]);
+    }
+
+    #[test]
+    fn strict_parsing_reports_the_first_violation() {
+        let error = |contents| match Dialect::Ini.parse_strict(contents) {
+            Err(ParseError::StrictViolation { line, column, .. }) => (line, column),
+            result => panic!("Expected a strict violation, got {result:?}"),
+        };
+
+        assert_eq!(
+            error(
+                "; comment
+[s]
+  a=1
+  a=2
+"
+            ),
+            (4, 3)
+        );
+        assert_eq!(
+            error(
+                "[s]
+a=1
+[t]
+[s]
+"
+            ),
+            (4, 1)
+        );
+        assert_eq!(
+            error(
+                "[s]
+	not an entry
+"
+            ),
+            (2, 2)
+        );
+        assert!(
+            Dialect::Ini
+                .parse_strict(
+                    "a=1
+[s]
+a=1
+[t]
+a=1
+"
+                )
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn ini_does_not_read_values_with_spaces() {
+        let contents = "[s]\nkey = two words\n";
+
+        assert_eq!(Dialect::Ini.find(contents, "key", Some("s")).unwrap(), None);
+        assert_eq!(Dialect::Editorconfig.find(contents, "key", Some("s")).unwrap(), Some("two words"));
+    }
+
+    #[test]
+    fn dialects_are_detected() {
+        let samples = [
+            ("name=demo\n[server]\nport=80\n", Dialect::Ini),
+            (
+                "[core]\n\tbare = false\n[remote \"origin\"]\n\turl = git@host:repo.git\n",
+                Dialect::Gitconfig,
+            ),
+            (
+                "[Unit]\nDescription=My app\n[Service]\nExecStart=/usr/bin/app --port 80\n",
+                Dialect::Systemd,
+            ),
+            ("! comment\napp.name: My app\napp.port = 80\n", Dialect::Properties),
+            ("# comment\nexport HOST=\"localhost\"\nPORT=80\n", Dialect::Dotenv),
+            ("root = true\n\n[*.{rs,toml}]\nindent_style = space\n", Dialect::Editorconfig),
+        ];
+
+        for (contents, dialect) in samples {
+            let guess = detect_dialect(contents);
+            assert_eq!(guess.dialect, dialect, "{contents}");
+            assert!(guess.confidence > 0.0, "{contents}");
+        }
+    }
+
+    #[test]
+    fn empty_files_are_ini_without_confidence() {
+        let guess = detect_dialect("");
+        assert_eq!(guess.dialect, Dialect::Ini);
+        assert_eq!(guess.confidence, 0.0);
+    }
+}
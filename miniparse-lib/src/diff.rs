@@ -0,0 +1,132 @@
+//! Semantic comparison of two parsed ini files.
+//!
+//! Because the comparison works on [`IniFile`]s, formatting-only differences like spacing, comments or blank lines are
+//! ignored. Of repeated keys, only the first occurrence is compared, matching [`IniSection::get_value_by_key`].
+
+use crate::models::{IniFile, IniSection};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ChangeKind<'content> {
+    Added { value: &'content str },
+    Removed { value: &'content str },
+    Changed { old: &'content str, new: &'content str },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change<'content> {
+    /// `None` for the global section
+    pub section: Option<&'content str>,
+    pub key: &'content str,
+    pub kind: ChangeKind<'content>,
+}
+
+fn is_first_occurrence(section: &IniSection<'_>, index: usize) -> bool {
+    let key = section.entries[index].key;
+    section.entries.iter().position(|entry| entry.key == key) == Some(index)
+}
+
+fn diff_sections<'content>(
+    section_name: Option<&'content str>,
+    old: Option<&IniSection<'content>>,
+    new: Option<&IniSection<'content>>,
+    changes: &mut Vec<Change<'content>>,
+) {
+    let empty_section = IniSection::new();
+    let old = old.unwrap_or(&empty_section);
+    let new = new.unwrap_or(&empty_section);
+
+    for (index, entry) in old.entries.iter().enumerate() {
+        if !is_first_occurrence(old, index) {
+            continue;
+        }
+
+        let kind = match new.get_value_by_key(entry.key) {
+            None => ChangeKind::Removed { value: entry.value },
+            Some(new_value) if new_value != entry.value => ChangeKind::Changed {
+                old: entry.value,
+                new: new_value,
+            },
+            Some(_) => continue,
+        };
+
+        changes.push(Change {
+            section: section_name,
+            key: entry.key,
+            kind,
+        });
+    }
+
+    for (index, entry) in new.entries.iter().enumerate() {
+        if is_first_occurrence(new, index) && old.get_value_by_key(entry.key).is_none() {
+            changes.push(Change {
+                section: section_name,
+                key: entry.key,
+                kind: ChangeKind::Added { value: entry.value },
+            });
+        }
+    }
+}
+
+/// Lists the keys that were added, removed or changed going from `old` to `new`.
+///
+/// Changes in the global section come first, followed by the named sections in alphabetical order.
+pub fn diff<'content>(old: &IniFile<'content>, new: &IniFile<'content>) -> Vec<Change<'content>> {
+    let mut changes = Vec::new();
+
+    diff_sections(None, old.get_global_section(), new.get_global_section(), &mut changes);
+
+    let mut section_names: Vec<&'content str> = old.sections.iter().chain(new.sections.iter()).map(|(name, _)| *name).collect();
+    section_names.sort();
+    section_names.dedup();
+
+    for section_name in section_names {
+        diff_sections(
+            Some(section_name),
+            old.get_section_by_name(section_name),
+            new.get_section_by_name(section_name),
+            &mut changes,
+        );
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::diff::{Change, ChangeKind, diff};
+    use crate::parse;
+
+    #[test]
+    fn formatting_differences_are_ignored() {
+        let old = parse("a=1\n[s]\nb=2\n").unwrap();
+        let new = parse("\n a = 1\n\n[s]\n  b=2   \n").unwrap();
+        assert!(diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn added_removed_and_changed_keys_are_listed() {
+        let old = parse("a=1\n[s]\nb=2\nc=3\n").unwrap();
+        let new = parse("a=1\n[s]\nb=20\n[t]\nd=4\n").unwrap();
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![
+                Change {
+                    section: Some("s"),
+                    key: "b",
+                    kind: ChangeKind::Changed { old: "2", new: "20" }
+                },
+                Change {
+                    section: Some("s"),
+                    key: "c",
+                    kind: ChangeKind::Removed { value: "3" }
+                },
+                Change {
+                    section: Some("t"),
+                    key: "d",
+                    kind: ChangeKind::Added { value: "4" }
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Format-preserving modifications of ini strings.
+//!
+//! Unlike [`parse`](crate::parse), these functions work on the raw lines of the file, so comments, blank lines,
+//! spacing and entry order survive the edit. Only the lines that are actually touched are rewritten.
+
+use crate::lines::{Line, LineKind, attached_comments_start, detect_line_ending, join_lines, new_line, split_lines};
+use crate::models::IniEntry;
+use crate::{ENTRY_KEY_GROUP_NAME, ENTRY_VALUE_GROUP_NAME, KEY_VALUE_REGEX, ParseError, SECTION_HEADER_REGEX, SECTION_NAME_GROUP_NAME};
+
+/// Makes sure the entry is read back exactly as it is written.
+pub(crate) fn validated_entry_line(key: &str, value: &str) -> Result<String, ParseError> {
+    let entry_line = IniEntry { key, value }.to_string();
+
+    let round_trips = KEY_VALUE_REGEX.captures(&entry_line).is_some_and(|captures| {
+        captures.name(ENTRY_KEY_GROUP_NAME).map(|m| m.as_str()) == Some(key)
+            && captures.name(ENTRY_VALUE_GROUP_NAME).map(|m| m.as_str()) == Some(value)
+    });
+
+    if round_trips {
+        Ok(entry_line)
+    } else {
+        Err(ParseError::InvalidEntry(entry_line))
+    }
+}
+
+/// Makes sure the section header is read back exactly as it is written.
+pub(crate) fn validated_section_header(section: &str) -> Result<String, ParseError> {
+    let header_line = format!("[{section}]");
+
+    let round_trips = SECTION_HEADER_REGEX
+        .captures(&header_line)
+        .is_some_and(|captures| captures.name(SECTION_NAME_GROUP_NAME).map(|m| m.as_str()) == Some(section));
+
+    if round_trips {
+        Ok(header_line)
+    } else {
+        Err(ParseError::InvalidSectionHeader(header_line))
+    }
+}
+
+fn is_entry_with_key(line: &Line<'_>, section: Option<&str>, key: &str) -> bool {
+    line.section == section && matches!(line.kind, LineKind::Entry { key: line_key, .. } if line_key == key)
+}
+
+/// Sets the value of `key` in the given section, or in the global section when `section` is `None`.
+///
+/// The first occurrence of the key has its value replaced in place. If the key does not exist yet, it is added
+/// after the last entry of the section. If the section does not exist yet, it is appended to the end of the file.
+pub fn set(ini_string: &str, section: Option<&str>, key: &str, value: &str) -> Result<String, ParseError> {
+    let entry_line = validated_entry_line(key, value)?;
+    let mut lines = split_lines(ini_string)?;
+
+    let existing_entry = lines.iter_mut().find(|line| is_entry_with_key(line, section, key));
+
+    if let Some(line) = existing_entry {
+        log::debug!("Key found, replacing its value");
+        if let LineKind::Entry { value_range, .. } = &line.kind {
+            line.text.replace_range(value_range.clone(), value);
+        }
+        return Ok(join_lines(&lines));
+    }
+
+    let line_ending = detect_line_ending(&lines);
+    let section_end = lines
+        .iter()
+        .rposition(|line| line.section == section && !matches!(line.kind, LineKind::Other));
+
+    match (section_end, section) {
+        (Some(index), _) => {
+            log::debug!("Section found, adding key after its last entry");
+            if lines[index].ending.is_empty() {
+                lines[index].ending = line_ending;
+            }
+            lines.insert(index + 1, new_line(entry_line, line_ending, section));
+        }
+        (None, None) => {
+            log::debug!("Global section has no entries, adding key to the top of the file");
+            lines.insert(0, new_line(entry_line, line_ending, None));
+        }
+        (None, Some(section_name)) => {
+            log::debug!("Section not found, appending it to the end of the file");
+            if let Some(last_line) = lines.last_mut() {
+                if last_line.ending.is_empty() {
+                    last_line.ending = line_ending;
+                }
+                lines.push(new_line(String::new(), line_ending, None));
+            }
+            lines.push(new_line(validated_section_header(section_name)?, line_ending, Some(section_name)));
+            lines.push(new_line(entry_line, line_ending, Some(section_name)));
+        }
+    }
+
+    Ok(join_lines(&lines))
+}
+
+/// Removes every occurrence of `key` from the given section, or from the global section when `section` is `None`.
+///
+/// Returns `None` when the key was not present, so the caller can decide whether that is an error.
+pub fn delete_key(ini_string: &str, section: Option<&str>, key: &str) -> Result<Option<String>, ParseError> {
+    let mut lines = split_lines(ini_string)?;
+    let line_count = lines.len();
+
+    lines.retain(|line| !is_entry_with_key(line, section, key));
+
+    if lines.len() == line_count {
+        log::debug!("Key not found, nothing to delete");
+        return Ok(None);
+    }
+
+    Ok(Some(join_lines(&lines)))
+}
+
+/// Removes the named section: the comments directly above its header, the header, and every line up to the comments
+/// directly above the next section header.
+///
+/// Returns `None` when the section was not present, so the caller can decide whether that is an error.
+pub fn delete_section(ini_string: &str, section: &str) -> Result<Option<String>, ParseError> {
+    let mut lines = split_lines(ini_string)?;
+
+    // Every section header starts a block that includes its attached comments, so each block can be removed as a whole
+    let block_starts = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| matches!(line.kind, LineKind::SectionHeader { .. }))
+        .map(|(index, line)| (attached_comments_start(&lines, index), line.section == Some(section)))
+        .collect::<Vec<_>>();
+    let block_ends = block_starts.iter().skip(1).map(|(start, _)| *start).chain([lines.len()]);
+    let removed_ranges = block_starts
+        .iter()
+        .zip(block_ends)
+        .filter(|((_, is_removed), _)| *is_removed)
+        .map(|((start, _), end)| *start..end)
+        .collect::<Vec<_>>();
+
+    let Some(first_index) = removed_ranges.first().map(|range| range.start) else {
+        log::debug!("Section not found, nothing to delete");
+        return Ok(None);
+    };
+
+    for range in removed_ranges.into_iter().rev() {
+        lines.drain(range);
+    }
+
+    // When the section was at the end of the file, the blank lines that separated it from the previous section
+    // would now dangle at the end of the file.
+    if first_index >= lines.len() {
+        while lines.last().is_some_and(|line| line.text.trim().is_empty()) {
+            lines.pop();
+        }
+    }
+
+    Ok(Some(join_lines(&lines)))
+}
+
+/// Renames every header of the section `old` to `new`, leaving the entries and comments of the section untouched.
+///
+/// Returns `None` when the section was not present. Renaming to a section that already exists is an error, since
+/// that would silently merge two sections.
+pub fn rename_section(ini_string: &str, old: &str, new: &str) -> Result<Option<String>, ParseError> {
+    validated_section_header(new)?;
+    let mut lines = split_lines(ini_string)?;
+
+    if lines.iter().any(|line| line.section == Some(new)) {
+        return Err(ParseError::AlreadyExists(new.to_string()));
+    }
+
+    let mut renamed = false;
+    for line in lines.iter_mut().filter(|line| line.section == Some(old)) {
+        if let LineKind::SectionHeader { name_range } = &line.kind {
+            line.text.replace_range(name_range.clone(), new);
+            renamed = true;
+        }
+    }
+
+    if !renamed {
+        log::debug!("Section not found, nothing to rename");
+        return Ok(None);
+    }
+
+    Ok(Some(join_lines(&lines)))
+}
+
+/// Renames every occurrence of the key `old` in the given section to `new`, keeping the position of the entries.
+///
+/// Returns `None` when the key was not present. Renaming to a key that already exists in the section is an error.
+pub fn rename_key(ini_string: &str, section: Option<&str>, old: &str, new: &str) -> Result<Option<String>, ParseError> {
+    let mut lines = split_lines(ini_string)?;
+
+    if lines.iter().any(|line| is_entry_with_key(line, section, new)) {
+        return Err(ParseError::AlreadyExists(new.to_string()));
+    }
+
+    let mut renamed = false;
+    for line in lines.iter_mut().filter(|line| is_entry_with_key(line, section, old)) {
+        if let LineKind::Entry { key_range, value_range, .. } = &line.kind {
+            validated_entry_line(new, &line.text[value_range.clone()])?;
+            line.text.replace_range(key_range.clone(), new);
+            renamed = true;
+        }
+    }
+
+    if !renamed {
+        log::debug!("Key not found, nothing to rename");
+        return Ok(None);
+    }
+
+    Ok(Some(join_lines(&lines)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::edit::{delete_key, delete_section, rename_key, rename_section, set};
+    use crate::find;
+
+    const INI: &str = "; settings\nname = demo\n\n[server]\nhost=localhost\n  port = 80   \n\n[client]\nretries = 3\n";
+
+    #[test]
+    fn set_replaces_existing_value_in_place() {
+        let edited = set(INI, Some("server"), "port", "8080").unwrap();
+        assert_eq!(edited, INI.replace("port = 80 ", "port = 8080 "));
+    }
+
+    #[test]
+    fn set_adds_key_after_last_entry_of_section() {
+        let edited = set(INI, Some("server"), "timeout", "5").unwrap();
+        assert_eq!(edited, INI.replace("  port = 80   \n", "  port = 80   \ntimeout = 5\n"));
+    }
+
+    #[test]
+    fn set_appends_missing_section() {
+        let edited = set(INI, Some("logging"), "level", "debug").unwrap();
+        assert_eq!(edited, format!("{INI}\n[logging]\nlevel = debug\n"));
+    }
+
+    #[test]
+    fn set_adds_global_key_to_top_when_global_section_is_empty() {
+        let edited = set("[server]\nhost=localhost", None, "name", "demo").unwrap();
+        assert_eq!(edited, "name = demo\n[server]\nhost=localhost");
+    }
+
+    #[test]
+    fn set_keeps_crlf_line_endings() {
+        let edited = set("[server]\r\nhost=localhost", Some("server"), "port", "80").unwrap();
+        assert_eq!(edited, "[server]\r\nhost=localhost\r\nport = 80\r\n");
+    }
+
+    #[test]
+    fn set_value_can_be_found_afterwards() {
+        let edited = set(INI, Some("client"), "retries", "7").unwrap();
+        assert_eq!(find(&edited, "retries", Some("client")).unwrap(), Some("7"));
+    }
+
+    #[test]
+    fn set_rejects_unrepresentable_value() {
+        assert!(set(INI, None, "name", "two words").is_err());
+    }
+
+    #[test]
+    fn delete_key_removes_only_that_line() {
+        let edited = delete_key(INI, Some("server"), "host").unwrap().unwrap();
+        assert_eq!(edited, INI.replace("host=localhost\n", ""));
+    }
+
+    #[test]
+    fn delete_key_reports_missing_key() {
+        assert!(delete_key(INI, Some("client"), "host").unwrap().is_none());
+    }
+
+    #[test]
+    fn delete_section_removes_up_to_next_header() {
+        let edited = delete_section(INI, "server").unwrap().unwrap();
+        assert_eq!(edited, "; settings\nname = demo\n\n[client]\nretries = 3\n");
+    }
+
+    #[test]
+    fn delete_last_section_removes_dangling_blank_lines() {
+        let edited = delete_section(INI, "client").unwrap().unwrap();
+        assert_eq!(edited, "; settings\nname = demo\n\n[server]\nhost=localhost\n  port = 80   \n");
+    }
+
+    #[test]
+    fn delete_section_leaves_comments_of_the_next_section() {
+        let ini_string = "name = demo\n\n[server]\nhost=localhost\n\n; about client\n[client]\nretries = 3\n";
+        let edited = delete_section(ini_string, "server").unwrap().unwrap();
+        assert_eq!(edited, "name = demo\n\n; about client\n[client]\nretries = 3\n");
+    }
+
+    #[test]
+    fn delete_section_removes_its_own_comments() {
+        let ini_string = "name = demo\n\n[server]\nhost=localhost\n\n; about client\n[client]\nretries = 3\n";
+        let edited = delete_section(ini_string, "client").unwrap().unwrap();
+        assert_eq!(edited, "name = demo\n\n[server]\nhost=localhost\n");
+    }
+
+    #[test]
+    fn rename_section_only_changes_header() {
+        let edited = rename_section(INI, "server", "backend").unwrap().unwrap();
+        assert_eq!(edited, INI.replace("[server]", "[backend]"));
+    }
+
+    #[test]
+    fn rename_section_refuses_to_merge() {
+        assert!(rename_section(INI, "server", "client").is_err());
+    }
+
+    #[test]
+    fn rename_key_keeps_value_and_spacing() {
+        let edited = rename_key(INI, Some("server"), "port", "listen_port").unwrap().unwrap();
+        assert_eq!(edited, INI.replace("  port = 80", "  listen_port = 80"));
+    }
+
+    #[test]
+    fn rename_key_reports_missing_key() {
+        assert!(rename_key(INI, None, "port", "listen_port").unwrap().is_none());
+    }
+
+    #[test]
+    fn delete_section_reports_missing_section() {
+        assert!(delete_section(INI, "logging").unwrap().is_none());
+    }
+}
@@ -0,0 +1,75 @@
+//! Ini files embedded in a binary at compile time, with the `include_ini!` macro of the `miniparse-macros` crate.
+//!
+//! ```ignore
+//! static DEFAULTS: EmbeddedIni = miniparse_macros::include_ini!("defaults.ini");
+//!
+//! let port = DEFAULTS.get(Some("server"), "port");
+//! ```
+
+use crate::models::{IniEntry, IniFile, IniSection};
+
+/// The entries of a section, as `(key, value)` pairs
+pub type EmbeddedSection = &'static [(&'static str, &'static str)];
+
+/// An ini file that was parsed at compile time. Duplicate keys and sections are rejected when the file is embedded, so
+/// every key occurs once.
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedIni {
+    pub global_section: EmbeddedSection,
+    /// Named sections, in the order they appear in the file
+    pub sections: &'static [(&'static str, EmbeddedSection)],
+}
+
+impl EmbeddedIni {
+    /// The entries of a named section, or of the global section for `None`.
+    pub fn section(&self, name: Option<&str>) -> Option<EmbeddedSection> {
+        match name {
+            Some(name) => self
+                .sections
+                .iter()
+                .find_map(|(section_name, section)| (*section_name == name).then_some(*section)),
+            None => Some(self.global_section),
+        }
+    }
+
+    pub fn get(&self, section: Option<&str>, key: &str) -> Option<&'static str> {
+        self.section(section)?
+            .iter()
+            .find_map(|(entry_key, value)| (*entry_key == key).then_some(*value))
+    }
+
+    /// Builds a regular [`IniFile`], to use the embedded file with the rest of the library.
+    pub fn to_ini_file(&self) -> IniFile<'static> {
+        let to_section = |entries: EmbeddedSection| IniSection {
+            entries: entries.iter().map(|(key, value)| IniEntry { key, value }).collect(),
+        };
+
+        IniFile {
+            global_section: (!self.global_section.is_empty()).then(|| to_section(self.global_section)),
+            sections: self.sections.iter().map(|(name, entries)| (*name, to_section(entries))).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::embedded::EmbeddedIni;
+
+    static EMBEDDED: EmbeddedIni = EmbeddedIni {
+        global_section: &[("name", "demo")],
+        sections: &[("server", &[("host", "localhost"), ("port", "80")])],
+    };
+
+    #[test]
+    fn values_are_looked_up() {
+        assert_eq!(EMBEDDED.get(None, "name"), Some("demo"));
+        assert_eq!(EMBEDDED.get(Some("server"), "port"), Some("80"));
+        assert_eq!(EMBEDDED.get(Some("client"), "port"), None);
+    }
+
+    #[test]
+    fn converts_to_ini_file() {
+        let ini_file = EMBEDDED.to_ini_file();
+        assert_eq!(ini_file.to_string(), "name = demo\n\n[server]\nhost = localhost\nport = 80\n");
+    }
+}
@@ -0,0 +1,115 @@
+//! Resolving `${section:key}` references to other values and `${ENV_VAR}` references to environment variables.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::ParseError;
+use crate::models::IniFile;
+
+/// `$$` for a literal dollar sign, or a `${...}` reference
+static REFERENCE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\$|\$\{([^{}]*)\}").expect("Invalid regex!"));
+
+/// Looks up a `section:key` reference. An empty section, like in `${:key}`, refers to the global section.
+fn look_up<'content>(ini_file: &IniFile<'content>, section_name: &str, key: &str) -> Option<&'content str> {
+    let section = if section_name.is_empty() {
+        ini_file.get_global_section()
+    } else {
+        ini_file.get_section_by_name(section_name)
+    };
+    section?.get_value_by_key(key)
+}
+
+fn expand_references(
+    value: &str,
+    ini_file: &IniFile<'_>,
+    env: &impl Fn(&str) -> Option<String>,
+    expanding: &mut Vec<String>,
+    unresolved: &mut Vec<String>,
+) -> Result<String, ParseError> {
+    let mut expanded = String::new();
+    let mut last_end = 0;
+
+    for captures in REFERENCE_REGEX.captures_iter(value) {
+        let whole_match = captures.get(0).expect("Group 0 is always the whole match");
+        expanded.push_str(&value[last_end..whole_match.start()]);
+        last_end = whole_match.end();
+
+        let Some(reference) = captures.get(1).map(|reference| reference.as_str()) else {
+            expanded.push('$');
+            continue;
+        };
+
+        let resolved = match reference.split_once(':') {
+            Some((section_name, key)) => match look_up(ini_file, section_name, key) {
+                Some(_) if expanding.iter().any(|outer| outer == reference) => {
+                    return Err(ParseError::InterpolationCycle(format!("${{{reference}}}")));
+                }
+                Some(referenced_value) => {
+                    // Referenced values may contain references themselves
+                    expanding.push(reference.to_string());
+                    let referenced_value = expand_references(referenced_value, ini_file, env, expanding, unresolved)?;
+                    expanding.pop();
+                    Some(referenced_value)
+                }
+                None => None,
+            },
+            None => env(reference),
+        };
+
+        match resolved {
+            Some(resolved) => expanded.push_str(&resolved),
+            None if !unresolved.iter().any(|known| known == reference) => unresolved.push(reference.to_string()),
+            None => (),
+        }
+    }
+
+    expanded.push_str(&value[last_end..]);
+    Ok(expanded)
+}
+
+/// Replaces every `${section:key}` reference in the value with the value of that key, and every `${NAME}` reference with
+/// the environment variable returned by `env`. Referenced values are expanded as well; `$$` is a literal `$`.
+///
+/// Fails with all references that could not be resolved, or when a value refers back to itself.
+pub fn expand(value: &str, ini_file: &IniFile<'_>, env: impl Fn(&str) -> Option<String>) -> Result<String, ParseError> {
+    let mut unresolved = Vec::new();
+    let expanded = expand_references(value, ini_file, &env, &mut Vec::new(), &mut unresolved)?;
+
+    if !unresolved.is_empty() {
+        return Err(ParseError::UnresolvedPlaceholders(unresolved.join(", ")));
+    }
+
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expand::expand;
+    use crate::parse;
+
+    fn env(name: &str) -> Option<String> {
+        (name == "HOME").then(|| "/home/user".to_string())
+    }
+
+    #[test]
+    fn references_are_expanded() {
+        let ini_file = parse("root=${HOME}/app\n[paths]\ndata=${:root}/data\ncache=${paths:data}/cache\n").unwrap();
+
+        assert_eq!(expand("${paths:cache}", &ini_file, env).unwrap(), "/home/user/app/data/cache");
+        assert_eq!(expand("$${HOME}costs$$5", &ini_file, env).unwrap(), "${HOME}costs$5");
+    }
+
+    #[test]
+    fn unresolved_references_are_an_error() {
+        let ini_file = parse("[s]\na=${s:missing}\n").unwrap();
+        let error = expand("${s:a}${USER}${USER}", &ini_file, env).unwrap_err();
+        assert_eq!(error.to_string(), "Unresolved placeholders: s:missing, USER");
+    }
+
+    #[test]
+    fn cycles_are_an_error() {
+        let ini_file = parse("[s]\na=${s:b}\nb=x${s:a}\n").unwrap();
+        assert!(expand("${s:a}", &ini_file, env).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Writes `contents` to `path` without ever leaving a half-written file behind.
+///
+/// The contents are written to a new temporary file next to the target first, which is then renamed over the target.
+/// The permissions of an existing target file are kept, and a symlinked target is written through, keeping the link.
+pub fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    // Renaming over a symlink would replace the link itself, so write next to the file it points to instead
+    let path = match fs::canonicalize(path) {
+        Ok(canonical_path) => canonical_path,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => path.to_path_buf(),
+        Err(error) => return Err(error),
+    };
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Path does not point to a file"))?;
+
+    let mut temp_file_name = file_name.to_os_string();
+    temp_file_name.push(format!(".{}.tmp", std::process::id()));
+    let temp_path = path.with_file_name(temp_file_name);
+
+    log::debug!("Writing to temporary file {}", temp_path.display());
+
+    // `create_new` refuses to follow a symlink or reuse a file someone else put at the temporary path
+    let mut temp_file = OpenOptions::new().write(true).create_new(true).open(&temp_path)?;
+
+    let write_result = (|| -> io::Result<()> {
+        // Set before writing, so the contents are never readable with looser permissions than the target's
+        if let Ok(metadata) = fs::metadata(&path) {
+            temp_file.set_permissions(metadata.permissions())?;
+        }
+
+        temp_file.write_all(contents.as_bytes())?;
+        temp_file.sync_all()?;
+
+        fs::rename(&temp_path, &path)
+    })();
+
+    if write_result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    write_result
+}
+
+/// Copies the file at `path` to a backup next to it, named after the file with `suffix` appended, like `sed -i.bak`.
+/// Returns the path of the backup.
+pub fn backup(path: &Path, suffix: &str) -> io::Result<PathBuf> {
+    let mut backup_path = OsString::from(path.as_os_str());
+    backup_path.push(suffix);
+    let backup_path = PathBuf::from(backup_path);
+
+    log::debug!("Backing up {} to {}", path.display(), backup_path.display());
+    fs::copy(path, &backup_path)?;
+
+    Ok(backup_path)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::{fs, os::unix::fs::PermissionsExt};
+
+    use crate::files::write_atomic;
+
+    #[test]
+    fn symlinks_and_permissions_are_kept() {
+        let directory = std::env::temp_dir().join(format!("miniparse-files-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let target = directory.join("target.ini");
+        let link = directory.join("link.ini");
+        fs::write(&target, "a=1\n").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o600)).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_atomic(&link, "a=2\n").unwrap();
+
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "a=2\n");
+        assert_eq!(fs::metadata(&target).unwrap().permissions().mode() & 0o777, 0o600);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
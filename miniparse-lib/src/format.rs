@@ -0,0 +1,101 @@
+//! Canonical formatting of ini strings, like rustfmt does for Rust code.
+
+use crate::ParseError;
+use crate::lines::{LineKind, detect_line_ending, split_lines};
+use crate::models::IniEntry;
+
+#[derive(Debug, PartialEq, Eq)]
+enum FormattedKind {
+    Blank,
+    Comment,
+    SectionHeader,
+    Entry,
+}
+
+/// Rewrites the ini string in a consistent layout, without changing what it means.
+///
+/// - Entries are written as `key = value` and section headers as `[name]`, without surrounding whitespace.
+/// - Trailing whitespace is trimmed from every other line.
+/// - Runs of blank lines are collapsed into one, and blank lines at the start and end of the file are removed.
+/// - Every section is preceded by exactly one blank line, placed before the comments directly above its header.
+/// - The file ends with a single line ending.
+pub fn format(ini_string: &str) -> Result<String, ParseError> {
+    let lines = split_lines(ini_string)?;
+    let line_ending = detect_line_ending(&lines);
+    let mut formatted: Vec<(String, FormattedKind)> = Vec::new();
+
+    for line in &lines {
+        match &line.kind {
+            LineKind::SectionHeader { name_range } => {
+                let comments_start = formatted
+                    .iter()
+                    .rposition(|(_, kind)| *kind != FormattedKind::Comment)
+                    .map_or(0, |index| index + 1);
+
+                if comments_start > 0 && formatted[comments_start - 1].1 != FormattedKind::Blank {
+                    formatted.insert(comments_start, (String::new(), FormattedKind::Blank));
+                }
+
+                formatted.push((format!("[{}]", &line.text[name_range.clone()]), FormattedKind::SectionHeader));
+            }
+            LineKind::Entry { key, .. } => {
+                let entry = IniEntry {
+                    key,
+                    value: line.value().unwrap_or_default(),
+                };
+                formatted.push((entry.to_string(), FormattedKind::Entry));
+            }
+            LineKind::Other if line.text.trim().is_empty() => {
+                let follows_content = formatted
+                    .last()
+                    .is_some_and(|(_, kind)| *kind != FormattedKind::Blank && *kind != FormattedKind::SectionHeader);
+
+                if follows_content {
+                    formatted.push((String::new(), FormattedKind::Blank));
+                }
+            }
+            LineKind::Other => formatted.push((line.text.trim_end().to_string(), FormattedKind::Comment)),
+        }
+    }
+
+    while formatted.last().is_some_and(|(_, kind)| *kind == FormattedKind::Blank) {
+        formatted.pop();
+    }
+
+    Ok(formatted.iter().flat_map(|(text, _)| [text.as_str(), line_ending]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::format::format;
+
+    #[test]
+    fn entries_and_headers_are_normalized() {
+        let formatted = format("  a=1   \n[s]   \n\tb   =   2\n").unwrap();
+        assert_eq!(formatted, "a = 1\n\n[s]\nb = 2\n");
+    }
+
+    #[test]
+    fn blank_lines_are_made_consistent() {
+        let formatted = format("\n\na = 1\n\n\n\nb = 2\n[s]\n\nc = 3\n\n\n").unwrap();
+        assert_eq!(formatted, "a = 1\n\nb = 2\n\n[s]\nc = 3\n");
+    }
+
+    #[test]
+    fn comments_stay_attached_to_their_section() {
+        let formatted = format("a = 1\n; about s   \n[s]\nc = 3").unwrap();
+        assert_eq!(formatted, "a = 1\n\n; about s\n[s]\nc = 3\n");
+    }
+
+    #[test]
+    fn commented_out_entries_are_left_unchanged() {
+        let formatted = format("[s]\n;k=v\n#c  :d\nk=v\n").unwrap();
+        assert_eq!(formatted, "[s]\n;k=v\n#c  :d\nk = v\n");
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let formatted = format("a=1\n; about s\n[s]\n\nc=3\n[t]\nd=4").unwrap();
+        assert_eq!(format(&formatted).unwrap(), formatted);
+    }
+}
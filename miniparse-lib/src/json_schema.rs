@@ -0,0 +1,278 @@
+//! Conversion between schemas and [JSON Schema](https://json-schema.org), so editors and other validators can check
+//! files against the same contract as [`IniSchema::validate`].
+//!
+//! Sections become objects with a property per key, and the value types map to the JSON Schema types `string`,
+//! `boolean`, `integer` and `number`. Required keys are listed in `required`, as are sections with a required key.
+//!
+//! Deprecated keys are properties marked `deprecated`, with a description naming their replacement. A required key that
+//! has deprecated keys standing in for it is required through `anyOf` instead, so any one of them satisfies it.
+//!
+//! Like [`convert::import`](crate::convert::import), importing produces schema text rather than an [`IniSchema`]. Pass
+//! the result to [`IniSchema::parse`] to get a schema.
+
+use serde_json::{Map, Value, json};
+
+use crate::ParseError;
+use crate::edit::{validated_entry_line, validated_section_header};
+use crate::schema::{DEPRECATED_PREFIX, Deprecation, IniSchema, KeySchema, ValueType};
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Deprecated properties are described as `Deprecated, use <replacement> instead`
+const REPLACEMENT_DESCRIPTION: (&str, &str) = ("Deprecated, use ", " instead");
+
+fn json_type(value_type: ValueType) -> &'static str {
+    match value_type {
+        ValueType::String => "string",
+        ValueType::Bool => "boolean",
+        ValueType::Int => "integer",
+        ValueType::Float => "number",
+    }
+}
+
+fn object_schema(keys: &[KeySchema<'_>], deprecations: &[Deprecation<'_>]) -> Map<String, Value> {
+    let mut properties = keys
+        .iter()
+        .map(|key_schema| (key_schema.key.to_string(), json!({ "type": json_type(key_schema.value_type) })))
+        .collect::<Map<_, _>>();
+    for deprecation in deprecations {
+        let replacement = keys.iter().find(|key_schema| key_schema.key == deprecation.replacement);
+        let value_type = replacement.map_or(ValueType::String, |key_schema| key_schema.value_type);
+        let (description_start, description_end) = REPLACEMENT_DESCRIPTION;
+        properties.insert(
+            deprecation.key.to_string(),
+            json!({
+                "type": json_type(value_type),
+                "deprecated": true,
+                "description": format!("{description_start}{}{description_end}", deprecation.replacement),
+            }),
+        );
+    }
+
+    // A required key with deprecated keys standing in for it is satisfied by any of them
+    let mut required = Vec::new();
+    let mut alternatives = Vec::new();
+    for key_schema in keys.iter().filter(|key_schema| key_schema.required) {
+        let deprecated_keys = deprecations
+            .iter()
+            .filter(|deprecation| deprecation.replacement == key_schema.key)
+            .map(|deprecation| deprecation.key)
+            .collect::<Vec<_>>();
+
+        if deprecated_keys.is_empty() {
+            required.push(json!(key_schema.key));
+        } else {
+            let any_of = [key_schema.key]
+                .into_iter()
+                .chain(deprecated_keys)
+                .map(|key| json!({ "required": [key] }));
+            alternatives.push(json!({ "anyOf": any_of.collect::<Vec<_>>() }));
+        }
+    }
+
+    let mut object = Map::new();
+    object.insert("type".to_string(), json!("object"));
+    object.insert("properties".to_string(), Value::Object(properties));
+    object.insert("required".to_string(), Value::Array(required));
+    if !alternatives.is_empty() {
+        object.insert("allOf".to_string(), Value::Array(alternatives));
+    }
+    object
+}
+
+fn section_deprecations<'schema, 'content>(schema: &'schema IniSchema<'content>, section: Option<&str>) -> &'schema [Deprecation<'content>] {
+    schema
+        .deprecations
+        .iter()
+        .find(|(deprecation_section, _)| *deprecation_section == section)
+        .map_or(&[][..], |(_, deprecations)| deprecations.as_slice())
+}
+
+/// Describes the schema as a JSON Schema document.
+pub fn export(schema: &IniSchema<'_>) -> String {
+    let global_keys = schema
+        .sections
+        .iter()
+        .find(|(section, _)| section.is_none())
+        .map_or(&[][..], |(_, keys)| keys.as_slice());
+
+    let mut root = Map::new();
+    root.insert("$schema".to_string(), json!(JSON_SCHEMA_DIALECT));
+    root.extend(object_schema(global_keys, section_deprecations(schema, None)));
+
+    for (section, keys) in &schema.sections {
+        let Some(section) = section else {
+            continue;
+        };
+
+        if let Some(Value::Object(properties)) = root.get_mut("properties") {
+            let deprecations = section_deprecations(schema, Some(section));
+            properties.insert(section.to_string(), Value::Object(object_schema(keys, deprecations)));
+        }
+        if keys.iter().any(|key_schema| key_schema.required)
+            && let Some(Value::Array(required)) = root.get_mut("required")
+        {
+            required.push(json!(section));
+        }
+    }
+
+    serde_json::to_string_pretty(&Value::Object(root)).expect("Values built from strings always serialize") + "\n"
+}
+
+fn type_name(key: &str, property: &Value) -> Result<&'static str, ParseError> {
+    let json_type = property.get("type").and_then(Value::as_str).unwrap_or_default();
+
+    match json_type {
+        "string" => Ok("string"),
+        "boolean" => Ok("bool"),
+        "integer" => Ok("int"),
+        "number" => Ok("float"),
+        _ => Err(ParseError::ConversionFailed(format!(
+            "Key {key} has no string, boolean, integer or number type"
+        ))),
+    }
+}
+
+/// The replacement named in the description of a deprecated property, or `None` when the property is not deprecated
+fn replacement<'property>(key: &str, property: &'property Value) -> Result<Option<&'property str>, ParseError> {
+    if property.get("deprecated").and_then(Value::as_bool) != Some(true) {
+        return Ok(None);
+    }
+
+    let (description_start, description_end) = REPLACEMENT_DESCRIPTION;
+    property
+        .get("description")
+        .and_then(Value::as_str)
+        .and_then(|description| description.strip_prefix(description_start)?.strip_suffix(description_end))
+        .map(Some)
+        .ok_or_else(|| ParseError::ConversionFailed(format!("Deprecated key {key} does not describe its replacement")))
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map_or(&[][..], Vec::as_slice)
+}
+
+/// Keys listed in `required`, or in the `required` of an `anyOf` alternative under `allOf`
+fn required_keys(object: &Value) -> Vec<&Value> {
+    let alternatives = array(object.get("allOf"))
+        .iter()
+        .flat_map(|all_of| array(all_of.get("anyOf")))
+        .flat_map(|any_of| array(any_of.get("required")));
+    array(object.get("required")).iter().chain(alternatives).collect()
+}
+
+fn is_object(property: &Value) -> bool {
+    property.get("type").and_then(Value::as_str) == Some("object") || property.get("properties").is_some()
+}
+
+/// Writes the keys of an object schema, then its nested objects as sections with dotted names.
+fn write_object(output: &mut String, section_name: Option<&str>, object: &Value) -> Result<(), ParseError> {
+    let properties = object.get("properties").and_then(Value::as_object).cloned().unwrap_or_default();
+    let required = required_keys(object);
+    let has_keys = properties.values().any(|property| !is_object(property));
+
+    if let Some(section_name) = section_name
+        && (has_keys || properties.is_empty())
+    {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&validated_section_header(section_name)?);
+        output.push('\n');
+    }
+
+    for (key, property) in properties.iter().filter(|(_, property)| !is_object(property)) {
+        let value = match replacement(key, property)? {
+            Some(replacement) => format!("{DEPRECATED_PREFIX}{replacement}"),
+            None => {
+                let type_name = type_name(key, property)?;
+                let optional = if required.iter().any(|required_key| *required_key == key) {
+                    ""
+                } else {
+                    "?"
+                };
+                format!("{type_name}{optional}")
+            }
+        };
+        output.push_str(&validated_entry_line(key, &value)?);
+        output.push('\n');
+    }
+
+    for (key, property) in properties.iter().filter(|(_, property)| is_object(property)) {
+        let nested_name = match section_name {
+            Some(section_name) => format!("{section_name}.{key}"),
+            None => key.clone(),
+        };
+        write_object(output, Some(&nested_name), property)?;
+    }
+
+    Ok(())
+}
+
+/// Converts a JSON Schema document into schema text.
+pub fn import(input: &str) -> Result<String, ParseError> {
+    let root: Value = serde_json::from_str(input).map_err(|err| ParseError::ConversionFailed(err.to_string()))?;
+    if !is_object(&root) {
+        return Err(ParseError::ConversionFailed("The top level must describe an object".to_string()));
+    }
+
+    let mut output = String::new();
+    write_object(&mut output, None, &root)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_schema::{export, import};
+    use crate::schema::IniSchema;
+
+    const SCHEMA: &str = "name = string\n\n[server]\ndebug = bool?\nport = int\n";
+
+    #[test]
+    fn export_describes_sections_and_types() {
+        let json = export(&IniSchema::parse(SCHEMA).unwrap());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["properties"]["name"]["type"], "string");
+        assert_eq!(value["properties"]["server"]["properties"]["port"]["type"], "integer");
+        assert_eq!(value["properties"]["server"]["required"], serde_json::json!(["port"]));
+        assert_eq!(value["required"], serde_json::json!(["name", "server"]));
+    }
+
+    #[test]
+    fn schemas_round_trip() {
+        let json = export(&IniSchema::parse(SCHEMA).unwrap());
+        assert_eq!(import(&json).unwrap(), SCHEMA);
+    }
+
+    #[test]
+    fn deprecated_keys_round_trip() {
+        let schema = "[server]\nhostname = string\nport = int?\nhost = deprecated:hostname\nlisten = deprecated:port\n";
+        let json = export(&IniSchema::parse(schema).unwrap());
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let server = &value["properties"]["server"];
+        assert_eq!(server["properties"]["host"]["deprecated"], true);
+        assert_eq!(server["properties"]["listen"]["type"], "integer");
+        assert_eq!(server["required"], serde_json::json!([]));
+        assert_eq!(
+            server["allOf"],
+            serde_json::json!([{"anyOf": [{"required": ["hostname"]}, {"required": ["host"]}]}])
+        );
+
+        assert_eq!(import(&json).unwrap(), schema);
+    }
+
+    #[test]
+    fn nested_objects_become_dotted_sections() {
+        let schema =
+            import(r#"{"type": "object", "properties": {"db": {"properties": {"replica": {"properties": {"port": {"type": "integer"}}}}}}}"#)
+                .unwrap();
+        assert_eq!(schema, "[db.replica]\nport = int?\n");
+    }
+
+    #[test]
+    fn unsupported_types_are_an_error() {
+        assert!(import(r#"{"properties": {"hosts": {"type": "array"}}}"#).is_err());
+    }
+}
@@ -1,5 +1,27 @@
+pub mod address;
 pub mod builders;
+pub mod cache;
+#[cfg(feature = "convert")]
+pub mod convert;
+pub mod dialect;
+pub mod diff;
+pub mod edit;
+pub mod embedded;
+pub mod expand;
+pub mod files;
+pub mod format;
+#[cfg(feature = "convert")]
+pub mod json_schema;
+mod lines;
+pub mod lint;
+pub mod merge;
+pub mod migrations;
 pub mod models;
+pub mod query;
+pub mod repair;
+pub mod schema;
+pub mod sort;
+pub mod template;
 
 use std::sync::LazyLock;
 
@@ -7,7 +29,9 @@ use regex::Regex;
 use thiserror::Error;
 
 use crate::builders::{IniFileBuilder, IniSectionBuilder};
-use crate::models::{IniEntry, IniFile, SectionId};
+use crate::dialect::Dialect;
+use crate::models::{IniFile, IniSection, SectionId};
+pub use crate::repair::repair;
 
 const ENTRY_KEY_GROUP_NAME: &str = "key";
 const ENTRY_VALUE_GROUP_NAME: &str = "value";
@@ -17,9 +41,37 @@ const SECTION_NAME_GROUP_NAME: &str = "section_name";
 pub enum ParseError {
     #[error("The group {0} was not found in the provided regex")]
     RegexCaptureGroupNotFound(&'static str),
+    #[error("The entry `{0}` would not be read back as written")]
+    InvalidEntry(String),
+    #[error("The section header `{0}` would not be read back as written")]
+    InvalidSectionHeader(String),
+    #[error("{0} already exists")]
+    AlreadyExists(String),
+    #[error("Unknown value type `{0}`")]
+    UnknownValueType(String),
+    #[error("Conflicting values for key {0}")]
+    MergeConflict(String),
+    #[error("Conversion failed: {0}")]
+    ConversionFailed(String),
+    #[error("Invalid query `{0}`")]
+    InvalidQuery(String),
+    #[error("Unresolved placeholders: {0}")]
+    UnresolvedPlaceholders(String),
+    #[error("Unknown lint rule `{0}`")]
+    UnknownLintRule(String),
+    #[error("Unknown dialect `{0}`")]
+    UnknownDialect(String),
+    #[error("{0} refers to itself")]
+    InterpolationCycle(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Migration failed: {0}")]
+    MigrationFailed(String),
+    #[error("Line {line}, column {column}: {message}")]
+    StrictViolation { line: usize, column: usize, message: String },
 }
 
-fn add_section_to_ini_builder<'content>(
+pub(crate) fn add_section_to_ini_builder<'content>(
     ini_file_builder: IniFileBuilder<'content>,
     current_section_builder: IniSectionBuilder<'content>,
 ) -> IniFileBuilder<'content> {
@@ -52,110 +104,47 @@ pub fn find<'content>(
     key_to_find: &'content str,
     section_to_find: Option<&'content str>,
 ) -> Result<Option<&'content str>, ParseError> {
-    let mut section_found = false;
-
-    for line in ini_string.lines().map(str::trim) {
-        log::debug!("Searching line: {line}");
-
-        if line.is_empty() {
-            log::debug!("Line is empty: skipping");
-            continue;
-        }
-
-        if let Some(section_to_find_name) = section_to_find {
-            if let Some(section_header_captures) = SECTION_HEADER_REGEX.captures(line) {
-                log::debug!("Found a new section header");
-
-                if section_found {
-                    // We found a new section, while already in the section we were trying to search through.
-                    // So the key wasn't present
-                    log::debug!("Searched through the specified section - key not found");
-                    return Ok(None);
-                }
-
-                let new_section_name = section_header_captures
-                    .name(SECTION_NAME_GROUP_NAME)
-                    .ok_or(ParseError::RegexCaptureGroupNotFound(SECTION_NAME_GROUP_NAME))?
-                    .as_str();
-
-                if new_section_name == section_to_find_name {
-                    log::debug!("Section header is the specified section - searching for specified key");
-                    section_found = true;
-                }
-
-                continue;
-            }
-
-            if !section_found {
-                // Still looking for the specified section
-                continue;
-            }
-        }
-
-        if let Some(key_value_captures) = KEY_VALUE_REGEX.captures(line) {
-            let key = key_value_captures
-                .name(ENTRY_KEY_GROUP_NAME)
-                .ok_or(ParseError::RegexCaptureGroupNotFound(ENTRY_KEY_GROUP_NAME))?
-                .as_str();
-
-            if key == key_to_find {
-                let value = key_value_captures
-                    .name(ENTRY_VALUE_GROUP_NAME)
-                    .ok_or(ParseError::RegexCaptureGroupNotFound(ENTRY_VALUE_GROUP_NAME))?
-                    .as_str();
-
-                return Ok(Some(value));
-            }
-        }
-    }
-
-    Ok(None)
+    Dialect::Ini.find(ini_string, key_to_find, section_to_find)
 }
 
-pub fn parse<'content>(ini_string: &'content str) -> Result<IniFile<'content>, ParseError> {
-    let mut ini_file_builder = IniFileBuilder::new();
-    let mut current_section_builder = IniSectionBuilder::new(SectionId::Global);
-
-    for line in ini_string.lines().map(str::trim) {
-        log::debug!("Parsing line: {line}");
-
-        if line.is_empty() {
-            log::debug!("Line is empty: skipping");
-            continue;
-        }
-
-        if let Some(key_value_captures) = KEY_VALUE_REGEX.captures(line) {
-            log::debug!("Line matched key-value regex.");
-            current_section_builder = current_section_builder.add_entry(IniEntry::try_from(key_value_captures)?);
-            continue;
-        }
-
-        if let Some(section_header_captures) = SECTION_HEADER_REGEX.captures(line) {
-            log::debug!("Line matched section start regex, adding current section");
-            ini_file_builder = add_section_to_ini_builder(ini_file_builder, current_section_builder);
+pub(crate) fn names_equal(a: &str, b: &str, ignore_case: bool) -> bool {
+    if ignore_case { a.to_lowercase() == b.to_lowercase() } else { a == b }
+}
 
-            let new_section_name = section_header_captures
-                .name(SECTION_NAME_GROUP_NAME)
-                .ok_or(ParseError::RegexCaptureGroupNotFound(SECTION_NAME_GROUP_NAME))?
-                .as_str();
+/// Like [`find`], but returns the values of every occurrence of the key instead of only the first one. When a section is
+/// given, the key is looked up in every occurrence of that section.
+pub fn find_all<'content>(ini_string: &'content str, key_to_find: &str, section_to_find: Option<&str>) -> Result<Vec<&'content str>, ParseError> {
+    Dialect::Ini.find_all(ini_string, key_to_find, section_to_find)
+}
 
-            current_section_builder = IniSectionBuilder::new(SectionId::Named(new_section_name));
-            continue;
-        }
+/// Like [`find_all`], matching section names and keys regardless of their case.
+pub fn find_all_ignore_case<'content>(
+    ini_string: &'content str,
+    key_to_find: &str,
+    section_to_find: Option<&str>,
+) -> Result<Vec<&'content str>, ParseError> {
+    Dialect::Ini.find_all_ignore_case(ini_string, key_to_find, section_to_find)
+}
 
-        log::warn!("Skipping unparsable non-empty line: {line}");
-    }
+pub fn parse<'content>(ini_string: &'content str) -> Result<IniFile<'content>, ParseError> {
+    Dialect::Ini.parse(ini_string)
+}
 
-    log::debug!("End of file reached. Adding current section, if we are building one.");
-    ini_file_builder = add_section_to_ini_builder(ini_file_builder, current_section_builder);
+/// Parses only the first section with the given name, stopping at the header after it. Cheaper than [`parse`] when only
+/// one section of a large file is needed. Returns `None` when the file has no such section.
+pub fn parse_section<'content>(ini_string: &'content str, section_name: &str) -> Result<Option<IniSection<'content>>, ParseError> {
+    Dialect::Ini.parse_section(ini_string, section_name)
+}
 
-    log::debug!("Building ini file");
-    Ok(ini_file_builder.build())
+/// Like [`parse`], but fails on anything it would otherwise skip or silently resolve: unparsable lines, keys that occur
+/// twice in a section, and sections that are defined twice. The error tells the line and column of the first problem.
+pub fn parse_strict<'content>(ini_string: &'content str) -> Result<IniFile<'content>, ParseError> {
+    Dialect::Ini.parse_strict(ini_string)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{IniFileBuilder, builders::IniSectionBuilder, find, parse};
+    use crate::{IniFileBuilder, builders::IniSectionBuilder, find, find_all, find_all_ignore_case, parse, parse_section};
 
     fn make_dummy_ini_string() -> String {
         let (_, global_section) = IniSectionBuilder::default()
@@ -256,4 +245,54 @@ mod tests {
         let found_value = find(dummy_ini_string.as_str(), "g_key2", None).unwrap().unwrap();
         assert_eq!(found_value, "g_value12")
     }
+
+    #[test]
+    fn find_all_returns_every_occurrence() {
+        let ini_string = "a=1\n[s]\nk=1\nk=2\n[t]\nk=3\n[s]\nk=4\n";
+
+        assert_eq!(find_all(ini_string, "k", Some("s")).unwrap(), vec!["1", "2", "4"]);
+        assert_eq!(find_all(ini_string, "k", None).unwrap(), vec!["1", "2", "3", "4"]);
+        assert!(find_all(ini_string, "k", Some("u")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn find_all_ignore_case_matches_any_case() {
+        let ini_string = "[Section]\nKey=1\nkey=2\n";
+
+        assert_eq!(find_all_ignore_case(ini_string, "KEY", Some("section")).unwrap(), vec!["1", "2"]);
+        assert!(find_all(ini_string, "KEY", Some("section")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_localized_falls_back_from_country_to_language_to_plain_key() {
+        let ini_file = parse("Name=Files\nName[de]=Dateien\nName[de_AT]=Dateien-AT\nName[sr@latin]=Datoteke\n").unwrap();
+        let section = ini_file.get_global_section().unwrap();
+
+        assert_eq!(section.get_localized("Name", "de_AT.UTF-8"), Some("Dateien-AT"));
+        assert_eq!(section.get_localized("Name", "de_DE"), Some("Dateien"));
+        assert_eq!(section.get_localized("Name", "sr_RS@latin"), Some("Datoteke"));
+        assert_eq!(section.get_localized("Name", "fr_FR"), Some("Files"));
+        assert_eq!(section.get_localized("Comment", "de"), None);
+    }
+
+    #[test]
+    fn repeated_sections_keep_the_first_one() {
+        let ini_string = "[server]\nhost=localhost\nport=80\n[client]\nretries=3\n[server]\nport=81\n";
+
+        let ini_file = parse(ini_string).unwrap();
+        let server = ini_file.get_section_by_name("server").unwrap();
+        assert_eq!(server.get_value_by_key("host"), Some("localhost"));
+        assert_eq!(server.get_value_by_key("port"), find(ini_string, "port", Some("server")).unwrap());
+        assert_eq!(ini_file.sections().count(), 2);
+    }
+
+    #[test]
+    fn parse_section_stops_at_the_next_header() {
+        let ini_string = "a=1\n[s]\nk=1\nl=2\n[t]\nk=3\n[s]\nk=4\n";
+
+        let section = parse_section(ini_string, "s").unwrap().unwrap();
+        assert_eq!(section.entries.len(), 2);
+        assert_eq!(section.get_value_by_key("l"), Some("2"));
+        assert!(parse_section(ini_string, "u").unwrap().is_none());
+    }
 }
@@ -0,0 +1,141 @@
+//! Line-level view of an ini string, shared by everything that needs to know where in the file something is.
+
+use std::ops::Range;
+
+use crate::{ENTRY_KEY_GROUP_NAME, ENTRY_VALUE_GROUP_NAME, KEY_VALUE_REGEX, ParseError, SECTION_HEADER_REGEX, SECTION_NAME_GROUP_NAME};
+
+/// Lines of plain ini files starting with one of these, after leading whitespace, are comments
+pub(crate) const COMMENT_PREFIXES: [char; 2] = ['#', ';'];
+
+#[derive(Debug)]
+pub(crate) enum LineKind<'content> {
+    SectionHeader {
+        name_range: Range<usize>,
+    },
+    Entry {
+        key: &'content str,
+        key_range: Range<usize>,
+        value_range: Range<usize>,
+    },
+    Other,
+}
+
+#[derive(Debug)]
+pub(crate) struct Line<'content> {
+    pub(crate) text: String,
+    pub(crate) ending: &'content str,
+    pub(crate) section: Option<&'content str>,
+    pub(crate) kind: LineKind<'content>,
+}
+
+impl Line<'_> {
+    /// The value of an entry line
+    pub(crate) fn value(&self) -> Option<&str> {
+        match &self.kind {
+            LineKind::Entry { value_range, .. } => Some(&self.text[value_range.clone()]),
+            _ => None,
+        }
+    }
+
+    /// The name of a section header line
+    pub(crate) fn section_name(&self) -> Option<&str> {
+        match &self.kind {
+            LineKind::SectionHeader { name_range } => Some(&self.text[name_range.clone()]),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_blank(&self) -> bool {
+        matches!(self.kind, LineKind::Other) && self.text.trim().is_empty()
+    }
+
+    /// Any non-blank line that is not a section header or entry, which includes unparsable lines.
+    pub(crate) fn is_comment(&self) -> bool {
+        matches!(self.kind, LineKind::Other) && !self.text.trim().is_empty()
+    }
+}
+
+pub(crate) fn split_lines(ini_string: &str) -> Result<Vec<Line<'_>>, ParseError> {
+    let mut lines = Vec::new();
+    let mut current_section = None;
+
+    for raw_line in ini_string.split_inclusive('\n') {
+        let text = raw_line.trim_end_matches(['\r', '\n']);
+        let ending = &raw_line[text.len()..];
+
+        // Commented-out entries like `;extension=curl` are comments, not entries
+        let kind = if text.trim_start().starts_with(COMMENT_PREFIXES) {
+            LineKind::Other
+        } else if let Some(section_header_captures) = SECTION_HEADER_REGEX.captures(text.trim()) {
+            let section_name = section_header_captures
+                .name(SECTION_NAME_GROUP_NAME)
+                .ok_or(ParseError::RegexCaptureGroupNotFound(SECTION_NAME_GROUP_NAME))?;
+
+            // The header regex ran on the trimmed line, so the match can not be used to borrow from `text`.
+            // Find the name back in the untrimmed line instead.
+            let name_start = text.find('[').unwrap_or_default() + 1;
+            let name_range = name_start..name_start + section_name.len();
+            current_section = Some(&text[name_range.clone()]);
+            LineKind::SectionHeader { name_range }
+        } else if let Some(key_value_captures) = KEY_VALUE_REGEX.captures(text) {
+            let key = key_value_captures
+                .name(ENTRY_KEY_GROUP_NAME)
+                .ok_or(ParseError::RegexCaptureGroupNotFound(ENTRY_KEY_GROUP_NAME))?;
+            let value = key_value_captures
+                .name(ENTRY_VALUE_GROUP_NAME)
+                .ok_or(ParseError::RegexCaptureGroupNotFound(ENTRY_VALUE_GROUP_NAME))?;
+
+            LineKind::Entry {
+                key: &text[key.range()],
+                key_range: key.range(),
+                value_range: value.range(),
+            }
+        } else {
+            LineKind::Other
+        };
+
+        lines.push(Line {
+            text: text.to_string(),
+            ending,
+            section: current_section,
+            kind,
+        });
+    }
+
+    Ok(lines)
+}
+
+/// Names a section in messages, like `[server]` or `the global section`
+pub(crate) fn describe_section(section: Option<&str>) -> String {
+    section.map_or("the global section".to_string(), |name| format!("[{name}]"))
+}
+
+/// Index of the first of the comment lines directly above `index`, which belong to the line at `index`.
+pub(crate) fn attached_comments_start(lines: &[Line<'_>], index: usize) -> usize {
+    let mut start = index;
+    while start > 0 && lines[start - 1].is_comment() {
+        start -= 1;
+    }
+    start
+}
+
+pub(crate) fn join_lines(lines: &[Line<'_>]) -> String {
+    lines.iter().flat_map(|line| [line.text.as_str(), line.ending]).collect()
+}
+
+/// Uses the line ending of the first line, so inserted lines match the rest of the file.
+pub(crate) fn detect_line_ending(lines: &[Line<'_>]) -> &'static str {
+    match lines.first() {
+        Some(line) if line.ending == "\r\n" => "\r\n",
+        _ => "\n",
+    }
+}
+
+pub(crate) fn new_line<'content>(text: String, ending: &'content str, section: Option<&'content str>) -> Line<'content> {
+    Line {
+        text,
+        ending,
+        section,
+        kind: LineKind::Other,
+    }
+}
@@ -0,0 +1,173 @@
+//! Checks for things that parse, but are likely mistakes.
+
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::ParseError;
+use crate::lines::{COMMENT_PREFIXES, LineKind, describe_section, split_lines};
+
+/// A line that would be an entry if it used `:` instead of `=` as delimiter
+static COLON_ENTRY_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*[^:=\s\[]+\s*:\s*[^:=\s]+\s*$").expect("Invalid regex!"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    /// A key occurs more than once in the same section. Only the first occurrence is used.
+    DuplicateKey,
+    /// A section header without any entries below it
+    EmptySection,
+    /// An entry using `:` as delimiter, in a file that uses `=`
+    MixedDelimiters,
+    /// A line that is not blank, a comment, a section header or an entry, and is skipped when parsing
+    UnparsableLine,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] = [Self::DuplicateKey, Self::EmptySection, Self::MixedDelimiters, Self::UnparsableLine];
+}
+
+impl TryFrom<&str> for LintRule {
+    type Error = ParseError;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.to_string() == name)
+            .ok_or_else(|| ParseError::UnknownLintRule(name.to_string()))
+    }
+}
+
+impl Display for LintRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateKey => write!(f, "duplicate-key"),
+            Self::EmptySection => write!(f, "empty-section"),
+            Self::MixedDelimiters => write!(f, "mixed-delimiters"),
+            Self::UnparsableLine => write!(f, "unparsable-line"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintFinding {
+    /// 1-based line number
+    pub line: usize,
+    pub rule: LintRule,
+    pub message: String,
+}
+
+impl Display for LintFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.rule, self.message)
+    }
+}
+
+fn is_comment(text: &str) -> bool {
+    text.trim_start().starts_with(COMMENT_PREFIXES)
+}
+
+/// Runs all lint rules on the ini string. Findings are ordered by line number.
+pub fn lint(ini_string: &str) -> Result<Vec<LintFinding>, ParseError> {
+    let lines = split_lines(ini_string)?;
+    let uses_equals_sign = lines.iter().any(|line| matches!(line.kind, LineKind::Entry { .. }));
+
+    let mut findings = Vec::new();
+    // Keys seen so far per section, with repeated sections counting as the same section
+    let mut seen_keys: Vec<(Option<&str>, &str)> = Vec::new();
+    // Line number and name of the last section header, while no entry has been found below it yet
+    let mut empty_section: Option<(usize, &str)> = None;
+
+    let report_empty_section = |empty_section: Option<(usize, &str)>, findings: &mut Vec<LintFinding>| {
+        if let Some((line, name)) = empty_section {
+            findings.push(LintFinding {
+                line,
+                rule: LintRule::EmptySection,
+                message: format!("Section [{name}] has no entries"),
+            });
+        }
+    };
+
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
+
+        match &line.kind {
+            LineKind::SectionHeader { .. } => {
+                report_empty_section(empty_section.take(), &mut findings);
+                empty_section = line.section.map(|name| (line_number, name));
+            }
+            LineKind::Entry { key, .. } => {
+                empty_section = None;
+
+                if seen_keys.contains(&(line.section, key)) {
+                    let section = describe_section(line.section);
+                    findings.push(LintFinding {
+                        line: line_number,
+                        rule: LintRule::DuplicateKey,
+                        message: format!("Key {key} already occurs earlier in {section}"),
+                    });
+                } else {
+                    seen_keys.push((line.section, key));
+                }
+            }
+            LineKind::Other if line.is_blank() || is_comment(&line.text) => (),
+            LineKind::Other if uses_equals_sign && COLON_ENTRY_REGEX.is_match(&line.text) => findings.push(LintFinding {
+                line: line_number,
+                rule: LintRule::MixedDelimiters,
+                message: format!("Entry `{}` uses `:` instead of `=`", line.text.trim()),
+            }),
+            LineKind::Other => findings.push(LintFinding {
+                line: line_number,
+                rule: LintRule::UnparsableLine,
+                message: format!("Line `{}` can not be parsed and is skipped", line.text.trim()),
+            }),
+        }
+    }
+    report_empty_section(empty_section, &mut findings);
+
+    findings.sort_by_key(|finding| finding.line);
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lint::{LintRule, lint};
+
+    fn rules(ini_string: &str) -> Vec<(usize, LintRule)> {
+        lint(ini_string).unwrap().iter().map(|finding| (finding.line, finding.rule)).collect()
+    }
+
+    #[test]
+    fn clean_file_has_no_findings() {
+        assert!(rules("; comment\n# comment\na=1\n\n[s]\nb=2\n").is_empty());
+    }
+
+    #[test]
+    fn commented_out_entries_have_no_findings() {
+        assert!(rules("[PHP]\nextension=curl\n;extension=curl\n;extension=gd\n# extension=gd\n").is_empty());
+    }
+
+    #[test]
+    fn all_rules_are_reported_with_line_numbers() {
+        let findings = rules("a=1\n[s]\nb=2\nb=3\n[empty]\n\n[t]\nc: 4\nnot an entry\nd=6\n[s]\nb=5\n[last]\n");
+        assert_eq!(
+            findings,
+            vec![
+                (4, LintRule::DuplicateKey),
+                (5, LintRule::EmptySection),
+                (8, LintRule::MixedDelimiters),
+                (9, LintRule::UnparsableLine),
+                (12, LintRule::DuplicateKey),
+                (13, LintRule::EmptySection),
+            ]
+        );
+    }
+
+    #[test]
+    fn rules_round_trip_through_their_names() {
+        for rule in LintRule::ALL {
+            assert_eq!(LintRule::try_from(rule.to_string().as_str()).unwrap(), rule);
+        }
+        assert!(LintRule::try_from("unknown").is_err());
+    }
+}
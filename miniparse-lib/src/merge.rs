@@ -0,0 +1,296 @@
+//! Layering of ini files on top of each other.
+
+use crate::ParseError;
+use crate::models::{IniEntry, IniFile, IniSection};
+
+/// What to do when both files contain the same key with different values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// The value of the overlay replaces the value of the base
+    #[default]
+    Override,
+    /// The value of the base is kept
+    KeepBase,
+    /// Conflicting values are an error
+    Error,
+}
+
+fn merge_section<'content>(
+    section_name: Option<&str>,
+    base: &mut IniSection<'content>,
+    overlay: IniSection<'content>,
+    strategy: MergeStrategy,
+) -> Result<(), ParseError> {
+    for entry in overlay.entries {
+        match base.entries.iter_mut().find(|base_entry| base_entry.key == entry.key) {
+            Some(base_entry) if base_entry.value == entry.value => (),
+            Some(base_entry) => match strategy {
+                MergeStrategy::Override => base_entry.value = entry.value,
+                MergeStrategy::KeepBase => (),
+                MergeStrategy::Error => {
+                    let path = match section_name {
+                        Some(section_name) => format!("{section_name}.{}", entry.key),
+                        None => entry.key.to_string(),
+                    };
+                    return Err(ParseError::MergeConflict(path));
+                }
+            },
+            None => base.entries.push(entry),
+        }
+    }
+
+    Ok(())
+}
+
+/// Merges `overlay` into `base`.
+///
+/// Keys and sections that only exist in the overlay are appended, after the keys and sections of the base. Keys that
+/// exist in both are resolved with the given strategy.
+pub fn merge<'content>(mut base: IniFile<'content>, overlay: IniFile<'content>, strategy: MergeStrategy) -> Result<IniFile<'content>, ParseError> {
+    if let Some(overlay_global_section) = overlay.global_section {
+        match &mut base.global_section {
+            Some(base_global_section) => merge_section(None, base_global_section, overlay_global_section, strategy)?,
+            None => base.global_section = Some(overlay_global_section),
+        }
+    }
+
+    for (section_name, overlay_section) in overlay.sections {
+        match base.get_section_by_name_mut(section_name) {
+            Some(base_section) => merge_section(Some(section_name), base_section, overlay_section, strategy)?,
+            None => base.sections.push((section_name, overlay_section)),
+        }
+    }
+
+    Ok(base)
+}
+
+/// A key that was changed differently on both sides of a [`merge3`]. `None` means the key is missing on that side.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Conflict<'content> {
+    /// `None` for the global section
+    pub section: Option<&'content str>,
+    pub key: &'content str,
+    pub base: Option<&'content str>,
+    pub ours: Option<&'content str>,
+    pub theirs: Option<&'content str>,
+}
+
+/// The result of a [`merge3`]
+#[derive(Debug)]
+pub struct ThreeWayMerge<'content> {
+    pub merged: IniFile<'content>,
+    pub conflicts: Vec<Conflict<'content>>,
+}
+
+fn value_of<'content>(section: Option<&IniSection<'content>>, key: &str) -> Option<&'content str> {
+    section?.get_value_by_key(key)
+}
+
+fn merge3_section<'content>(
+    section_name: Option<&'content str>,
+    [base, ours, theirs]: [Option<&IniSection<'content>>; 3],
+    conflicts: &mut Vec<Conflict<'content>>,
+) -> IniSection<'content> {
+    let mut keys: Vec<&'content str> = Vec::new();
+    for entry in ours.into_iter().chain(theirs).flat_map(|section| &section.entries) {
+        if !keys.contains(&entry.key) {
+            keys.push(entry.key);
+        }
+    }
+
+    let mut merged = IniSection::new();
+    for key in keys {
+        let (base_value, our_value, their_value) = (value_of(base, key), value_of(ours, key), value_of(theirs, key));
+
+        let value = if our_value == their_value || their_value == base_value {
+            our_value
+        } else if our_value == base_value {
+            their_value
+        } else {
+            conflicts.push(Conflict {
+                section: section_name,
+                key,
+                base: base_value,
+                ours: our_value,
+                theirs: their_value,
+            });
+            our_value
+        };
+
+        if let Some(value) = value {
+            merged.entries.push(IniEntry { key, value });
+        }
+    }
+
+    merged
+}
+
+/// Merges the changes that `ours` and `theirs` both made to `base`, like git does for the lines of a file.
+///
+/// Every key is compared on its own: a key changed or removed on one side only gets the change of that side, and a key
+/// changed the same way on both sides gets that change. Keys changed differently on both sides are reported as
+/// conflicts, and keep our value in the merged file. Our order of sections and keys is kept, with additions from their
+/// side appended.
+pub fn merge3<'content>(base: &IniFile<'content>, ours: &IniFile<'content>, theirs: &IniFile<'content>) -> ThreeWayMerge<'content> {
+    let mut conflicts = Vec::new();
+
+    let global_sections = [base, ours, theirs].map(IniFile::get_global_section);
+    let global_section = merge3_section(None, global_sections, &mut conflicts);
+
+    let mut section_names: Vec<&'content str> = Vec::new();
+    for (section_name, _) in ours.sections().chain(theirs.sections()) {
+        if !section_names.contains(&section_name) {
+            section_names.push(section_name);
+        }
+    }
+
+    let mut sections = Vec::new();
+    for section_name in section_names {
+        let [base_section, our_section, their_section] = [base, ours, theirs].map(|ini_file| ini_file.get_section_by_name(section_name));
+        let section = merge3_section(Some(section_name), [base_section, our_section, their_section], &mut conflicts);
+
+        // Sections left without entries are kept when both sides still have them
+        if !section.entries.is_empty() || (our_section.is_some() && their_section.is_some()) {
+            sections.push((section_name, section));
+        }
+    }
+
+    ThreeWayMerge {
+        merged: IniFile {
+            global_section: (!global_section.entries.is_empty()).then_some(global_section),
+            sections,
+        },
+        conflicts,
+    }
+}
+
+/// A value together with the layer it came from, as resolved by [`Layers::resolve`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Provenance<'layers, 'content> {
+    pub value: &'content str,
+    /// Name of the layer that supplied the value
+    pub source: &'layers str,
+    /// The values of lower layers that were overridden, from highest to lowest precedence
+    pub overridden: Vec<(&'layers str, &'content str)>,
+}
+
+/// Ini files layered on top of each other, later layers taking precedence like with [`MergeStrategy::Override`]. Unlike
+/// a merged file, every value can still be traced back to the layer it came from.
+#[derive(Debug, Default)]
+pub struct Layers<'content> {
+    layers: Vec<(String, IniFile<'content>)>,
+}
+
+impl<'content> Layers<'content> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer on top of the existing ones. The name is reported as the source of its values.
+    pub fn push(&mut self, name: impl Into<String>, ini_file: IniFile<'content>) {
+        self.layers.push((name.into(), ini_file));
+    }
+
+    /// The effective value of the key in the given section, or in the global section when `section` is `None`.
+    pub fn resolve(&self, section: Option<&str>, key: &str) -> Option<Provenance<'_, 'content>> {
+        let mut values = self.layers.iter().rev().filter_map(|(name, ini_file)| {
+            let section = match section {
+                Some(section_name) => ini_file.get_section_by_name(section_name),
+                None => ini_file.get_global_section(),
+            };
+            Some((name.as_str(), section?.get_value_by_key(key)?))
+        });
+
+        let (source, value) = values.next()?;
+        Some(Provenance {
+            value,
+            source,
+            overridden: values.collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::merge::{Conflict, Layers, MergeStrategy, Provenance, merge, merge3};
+    use crate::parse;
+
+    const BASE: &str = "a=1\n[s]\nb=2\nc=3\n";
+    const OVERLAY: &str = "[s]\nc=30\nd=4\n[t]\ne=5\n";
+
+    #[test]
+    fn override_takes_overlay_values() {
+        let merged = merge(parse(BASE).unwrap(), parse(OVERLAY).unwrap(), MergeStrategy::Override).unwrap();
+        assert_eq!(merged.to_string(), "a = 1\n\n[s]\nb = 2\nc = 30\nd = 4\n\n[t]\ne = 5\n");
+    }
+
+    #[test]
+    fn keep_base_keeps_base_values() {
+        let merged = merge(parse(BASE).unwrap(), parse(OVERLAY).unwrap(), MergeStrategy::KeepBase).unwrap();
+        assert_eq!(merged.get_section_by_name("s").unwrap().get_value_by_key("c"), Some("3"));
+        assert_eq!(merged.get_section_by_name("s").unwrap().get_value_by_key("d"), Some("4"));
+    }
+
+    #[test]
+    fn error_strategy_reports_conflict() {
+        assert!(merge(parse(BASE).unwrap(), parse(OVERLAY).unwrap(), MergeStrategy::Error).is_err());
+    }
+
+    #[test]
+    fn layers_resolve_with_provenance() {
+        let mut layers = Layers::new();
+        layers.push("base.ini", parse(BASE).unwrap());
+        layers.push("overlay.ini", parse(OVERLAY).unwrap());
+
+        assert_eq!(
+            layers.resolve(Some("s"), "c"),
+            Some(Provenance {
+                value: "30",
+                source: "overlay.ini",
+                overridden: vec![("base.ini", "3")],
+            })
+        );
+        assert_eq!(layers.resolve(None, "a").unwrap().source, "base.ini");
+        assert!(layers.resolve(Some("t"), "a").is_none());
+    }
+
+    #[test]
+    fn merge3_takes_changes_from_both_sides() {
+        let base = parse("a=1\n[s]\nb=2\nc=3\nd=4\n[gone]\nx=1\n").unwrap();
+        let ours = parse("a=10\n[s]\nb=2\nc=30\nd=4\n[gone]\nx=1\n").unwrap();
+        let theirs = parse("a=1\n[s]\nc=30\nd=40\ne=5\n[new]\ny=1\n").unwrap();
+
+        let result = merge3(&base, &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.merged.to_string(), "a = 10\n\n[s]\nc = 30\nd = 40\ne = 5\n\n[new]\ny = 1\n");
+    }
+
+    #[test]
+    fn merge3_reports_conflicts() {
+        let base = parse("[s]\nk=1\nr=1\n").unwrap();
+        let ours = parse("[s]\nk=2\nr=2\n").unwrap();
+        let theirs = parse("[s]\nk=3\n").unwrap();
+
+        let result = merge3(&base, &ours, &theirs);
+        assert_eq!(
+            result.conflicts,
+            vec![
+                Conflict {
+                    section: Some("s"),
+                    key: "k",
+                    base: Some("1"),
+                    ours: Some("2"),
+                    theirs: Some("3"),
+                },
+                Conflict {
+                    section: Some("s"),
+                    key: "r",
+                    base: Some("1"),
+                    ours: Some("2"),
+                    theirs: None,
+                },
+            ]
+        );
+        assert_eq!(result.merged.get_section_by_name("s").unwrap().get_value_by_key("k"), Some("2"));
+    }
+}
@@ -0,0 +1,298 @@
+//! Upgrades of configuration files written for older versions of a program.
+//!
+//! Register the steps that bring a file to each version, in increasing order of version. The version of a file is the
+//! `config_version` key in its global section, and files without it are at version 0. [`Migrations::migrate`] applies
+//! the steps of every version after the one of the file, and then sets `config_version` to the latest version.
+//!
+//! ```
+//! use miniparse::migrations::{Migrations, Step};
+//! use miniparse::models::OwnedIniFile;
+//!
+//! let migrations = Migrations::new()
+//!     .version(1, vec![Step::rename_key(Some("server"), "host", "hostname")])
+//!     .version(2, vec![Step::move_key("timeout", Some("server"), Some("client"))]);
+//!
+//! let mut config = OwnedIniFile::from(&miniparse::parse("[server]\nhost=localhost\ntimeout=30\n").unwrap());
+//! let report = migrations.migrate(&mut config).unwrap();
+//! assert_eq!(report.applied.len(), 2);
+//! ```
+
+use std::fmt::{Debug, Display};
+
+use crate::ParseError;
+use crate::lines::describe_section;
+use crate::models::OwnedIniFile;
+
+/// Key in the global section that holds the version of a file
+pub const VERSION_KEY: &str = "config_version";
+
+type Transform = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+enum StepKind {
+    RenameKey {
+        section: Option<String>,
+        from: String,
+        to: String,
+    },
+    MoveKey {
+        key: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    TransformValue {
+        section: Option<String>,
+        key: String,
+        transform: Transform,
+    },
+}
+
+/// A single change to a file. Steps whose key is not in the file are skipped.
+pub struct Step(StepKind);
+
+impl Step {
+    /// Renames a key, keeping its place in the section. An existing key with the new name is replaced.
+    pub fn rename_key(section: Option<&str>, from: &str, to: &str) -> Self {
+        Self(StepKind::RenameKey {
+            section: section.map(str::to_string),
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Moves a key to the end of another section, which is created when needed. An existing key in that section is
+    /// replaced.
+    pub fn move_key(key: &str, from: Option<&str>, to: Option<&str>) -> Self {
+        Self(StepKind::MoveKey {
+            key: key.to_string(),
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+        })
+    }
+
+    /// Replaces the value of a key by the result of `transform`.
+    pub fn transform_value(section: Option<&str>, key: &str, transform: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self(StepKind::TransformValue {
+            section: section.map(str::to_string),
+            key: key.to_string(),
+            transform: Box::new(transform),
+        })
+    }
+
+    /// Applies the step, returning whether the file had the key.
+    fn apply(&self, ini_file: &mut OwnedIniFile) -> bool {
+        match &self.0 {
+            StepKind::RenameKey { section, from, to } => {
+                let Some(section) = ini_file.section_mut(section.as_deref()) else {
+                    return false;
+                };
+                if section.get_value_by_key(from).is_none() {
+                    return false;
+                }
+
+                if from != to {
+                    section.remove(to);
+                }
+                for (key, _) in section.entries.iter_mut().filter(|(key, _)| key == from) {
+                    key.clone_from(to);
+                }
+                true
+            }
+            StepKind::MoveKey { key, from, to } => {
+                let Some(value) = ini_file.section_mut(from.as_deref()).and_then(|section| section.remove(key)) else {
+                    return false;
+                };
+
+                ini_file.section_mut_or_insert(to.as_deref()).set(key, &value);
+                true
+            }
+            StepKind::TransformValue { section, key, transform } => {
+                let Some(section) = ini_file.section_mut(section.as_deref()) else {
+                    return false;
+                };
+
+                let mut found = false;
+                for (_, value) in section.entries.iter_mut().filter(|(entry_key, _)| entry_key == key) {
+                    *value = transform(value);
+                    found = true;
+                }
+                found
+            }
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            StepKind::RenameKey { section, from, to } => write!(f, "Renamed {from} in {} to {to}", describe_section(section.as_deref())),
+            StepKind::MoveKey { key, from, to } => write!(
+                f,
+                "Moved {key} from {} to {}",
+                describe_section(from.as_deref()),
+                describe_section(to.as_deref())
+            ),
+            StepKind::TransformValue { section, key, .. } => write!(f, "Transformed the value of {key} in {}", describe_section(section.as_deref())),
+        }
+    }
+}
+
+impl Debug for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Step").field(&self.to_string()).finish()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AppliedStep {
+    /// The version the step belongs to
+    pub version: u32,
+    pub description: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    /// The steps that changed the file, in the order they were applied
+    pub applied: Vec<AppliedStep>,
+}
+
+/// The ordered migration steps of a program's configuration files
+#[derive(Debug, Default)]
+pub struct Migrations {
+    versions: Vec<(u32, Vec<Step>)>,
+}
+
+impl Migrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the steps that upgrade a file from the previous version to `version`.
+    pub fn version(mut self, version: u32, steps: Vec<Step>) -> Self {
+        self.versions.push((version, steps));
+        self
+    }
+
+    /// The version of files after they are migrated
+    pub fn latest_version(&self) -> u32 {
+        self.versions.iter().map(|(version, _)| *version).max().unwrap_or_default()
+    }
+
+    /// Upgrades the file to the latest version. Fails without changing the file when its `config_version` is not a
+    /// number or newer than the latest version, or when the versions were not registered in increasing order.
+    pub fn migrate(&self, ini_file: &mut OwnedIniFile) -> Result<MigrationReport, ParseError> {
+        if !self.versions.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+            return Err(ParseError::MigrationFailed("versions must be registered in increasing order".to_string()));
+        }
+
+        let from_version = match ini_file.get_global_section().and_then(|section| section.get_value_by_key(VERSION_KEY)) {
+            Some(value) => value
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| ParseError::MigrationFailed(format!("{VERSION_KEY} `{value}` is not a version number")))?,
+            None => 0,
+        };
+        let to_version = self.latest_version();
+        if from_version > to_version {
+            return Err(ParseError::MigrationFailed(format!(
+                "{VERSION_KEY} {from_version} is newer than the latest version {to_version}"
+            )));
+        }
+
+        let mut applied = Vec::new();
+        for (version, steps) in self.versions.iter().filter(|(version, _)| *version > from_version) {
+            for step in steps {
+                if step.apply(ini_file) {
+                    applied.push(AppliedStep {
+                        version: *version,
+                        description: step.to_string(),
+                    });
+                }
+            }
+        }
+
+        if to_version > from_version {
+            ini_file.section_mut_or_insert(None).set(VERSION_KEY, &to_version.to_string());
+        }
+
+        Ok(MigrationReport {
+            from_version,
+            to_version,
+            applied,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::migrations::{AppliedStep, Migrations, Step};
+    use crate::models::OwnedIniFile;
+    use crate::parse;
+
+    fn migrations() -> Migrations {
+        Migrations::new()
+            .version(1, vec![Step::rename_key(Some("server"), "host", "hostname")])
+            .version(
+                2,
+                vec![
+                    Step::move_key("timeout", Some("server"), Some("client")),
+                    Step::transform_value(Some("client"), "timeout", |value| format!("{value}s")),
+                    Step::rename_key(None, "missing", "still_missing"),
+                ],
+            )
+    }
+
+    fn owned(ini_string: &str) -> OwnedIniFile {
+        OwnedIniFile::from(&parse(ini_string).unwrap())
+    }
+
+    #[test]
+    fn old_files_are_upgraded_step_by_step() {
+        let mut ini_file = owned("[server]\nhost=localhost\ntimeout=30\n");
+        let report = migrations().migrate(&mut ini_file).unwrap();
+
+        assert_eq!((report.from_version, report.to_version), (0, 2));
+        assert_eq!(
+            report.applied,
+            vec![
+                AppliedStep {
+                    version: 1,
+                    description: "Renamed host in [server] to hostname".to_string()
+                },
+                AppliedStep {
+                    version: 2,
+                    description: "Moved timeout from [server] to [client]".to_string()
+                },
+                AppliedStep {
+                    version: 2,
+                    description: "Transformed the value of timeout in [client]".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            ini_file.as_ini_file().to_string(),
+            "config_version = 2\n\n[server]\nhostname = localhost\n\n[client]\ntimeout = 30s\n"
+        );
+    }
+
+    #[test]
+    fn only_newer_steps_are_applied() {
+        let mut ini_file = owned("config_version=1\n[server]\nhostname=localhost\nhost=old\n");
+        let report = migrations().migrate(&mut ini_file).unwrap();
+
+        assert!(report.applied.is_empty());
+        let server = ini_file.get_section_by_name("server").unwrap();
+        assert_eq!(server.get_value_by_key("host"), Some("old"));
+        assert_eq!(ini_file.get_global_section().unwrap().get_value_by_key("config_version"), Some("2"));
+    }
+
+    #[test]
+    fn unknown_versions_are_an_error() {
+        assert!(migrations().migrate(&mut owned("config_version=3\n")).is_err());
+        assert!(migrations().migrate(&mut owned("config_version=two\n")).is_err());
+
+        let unordered = Migrations::new().version(2, Vec::new()).version(1, Vec::new());
+        assert!(unordered.migrate(&mut owned("")).is_err());
+    }
+}
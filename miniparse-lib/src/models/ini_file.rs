@@ -1,11 +1,13 @@
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
 
+use crate::models::entry::IniEntry;
 use crate::models::section::IniSection;
 
 #[derive(Debug, Default)]
 pub struct IniFile<'content> {
     pub(crate) global_section: Option<IniSection<'content>>,
-    pub(crate) sections: HashMap<&'content str, IniSection<'content>>,
+    /// Named sections, in the order they appear in the file
+    pub(crate) sections: Vec<(&'content str, IniSection<'content>)>,
 }
 
 impl<'content> IniFile<'content> {
@@ -14,7 +16,44 @@ impl<'content> IniFile<'content> {
     }
 
     pub fn get_section_by_name(&self, name: &str) -> Option<&IniSection<'content>> {
-        self.sections.get(name)
+        self.sections
+            .iter()
+            .find_map(|(section_name, section)| if *section_name == name { Some(section) } else { None })
+    }
+
+    /// Like [`IniFile::get_section_by_name`], matching the name regardless of its case. Returns the first match.
+    pub fn get_section_by_name_ignore_case(&self, name: &str) -> Option<&IniSection<'content>> {
+        let name = name.to_lowercase();
+        self.sections
+            .iter()
+            .find_map(|(section_name, section)| if section_name.to_lowercase() == name { Some(section) } else { None })
+    }
+
+    /// The named sections, in the order they appear in the file.
+    pub fn sections(&self) -> impl Iterator<Item = (&'content str, &IniSection<'content>)> {
+        self.sections.iter().map(|(section_name, section)| (*section_name, section))
+    }
+
+    /// All entries of the global section and the named sections, in the order they appear in the file.
+    pub fn entries(&self) -> impl Iterator<Item = &IniEntry<'content>> {
+        self.global_section
+            .iter()
+            .chain(self.sections.iter().map(|(_, section)| section))
+            .flat_map(|section| section.entries.iter())
+    }
+
+    /// Like [`IniFile::entries`], for changing their values in place.
+    pub fn entries_mut(&mut self) -> impl Iterator<Item = &mut IniEntry<'content>> {
+        self.global_section
+            .iter_mut()
+            .chain(self.sections.iter_mut().map(|(_, section)| section))
+            .flat_map(|section| section.entries.iter_mut())
+    }
+
+    pub(crate) fn get_section_by_name_mut(&mut self, name: &str) -> Option<&mut IniSection<'content>> {
+        self.sections
+            .iter_mut()
+            .find_map(|(section_name, section)| if *section_name == name { Some(section) } else { None })
     }
 }
 
@@ -23,7 +62,10 @@ impl<'content> Display for IniFile<'content> {
         if let Some(global_section) = self.get_global_section() {
             writeln!(f, "{global_section}")?;
         }
-        for (section_name, section) in self.sections.iter() {
+        for (index, (section_name, section)) in self.sections.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
             writeln!(f, "[{section_name}]")?;
             write!(f, "{section}")?;
         }
@@ -1,9 +1,11 @@
 mod entry;
 mod ini_file;
+mod owned;
 mod section;
 mod section_id;
 
 pub use entry::IniEntry;
 pub use ini_file::IniFile;
+pub use owned::{OwnedIniFile, OwnedIniSection};
 pub use section::IniSection;
 pub use section_id::SectionId;
@@ -0,0 +1,127 @@
+use crate::models::{IniEntry, IniFile, IniSection};
+
+/// An [`IniFile`] that owns its names and values, so it can outlive the string it was parsed from.
+///
+/// Use [`OwnedIniFile::as_ini_file`] to get a borrowed view for the functions that take an [`IniFile`]. Creating the view
+/// does not parse anything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnedIniFile {
+    global_section: Option<OwnedIniSection>,
+    sections: Vec<(String, OwnedIniSection)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OwnedIniSection {
+    /// Keys and their values, in the order they appear in the file
+    pub entries: Vec<(String, String)>,
+}
+
+impl OwnedIniSection {
+    pub fn get_value_by_key(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find_map(|(entry_key, value)| if entry_key == key { Some(value.as_str()) } else { None })
+    }
+
+    /// Sets the value of the first occurrence of the key, or adds the key at the end of the section.
+    pub fn set(&mut self, key: &str, value: &str) {
+        match self.entries.iter_mut().find(|(entry_key, _)| entry_key == key) {
+            Some((_, entry_value)) => *entry_value = value.to_string(),
+            None => self.entries.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    /// Removes every occurrence of the key, returning the value of the first one.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.entries.iter().position(|(entry_key, _)| entry_key == key)?;
+        let (_, value) = self.entries.remove(index);
+        self.entries.retain(|(entry_key, _)| entry_key != key);
+        Some(value)
+    }
+
+    fn as_ini_section(&self) -> IniSection<'_> {
+        IniSection {
+            entries: self.entries.iter().map(|(key, value)| IniEntry { key, value }).collect(),
+        }
+    }
+}
+
+impl From<&IniSection<'_>> for OwnedIniSection {
+    fn from(section: &IniSection<'_>) -> Self {
+        Self {
+            entries: section
+                .entries
+                .iter()
+                .map(|entry| (entry.key.to_string(), entry.value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl OwnedIniFile {
+    pub fn get_global_section(&self) -> Option<&OwnedIniSection> {
+        self.global_section.as_ref()
+    }
+
+    pub fn get_section_by_name(&self, name: &str) -> Option<&OwnedIniSection> {
+        self.sections
+            .iter()
+            .find_map(|(section_name, section)| if section_name == name { Some(section) } else { None })
+    }
+
+    /// Like [`OwnedIniFile::get_section_by_name`], for changing the section in place. `None` is the global section.
+    pub fn section_mut(&mut self, name: Option<&str>) -> Option<&mut OwnedIniSection> {
+        match name {
+            Some(name) => self
+                .sections
+                .iter_mut()
+                .find_map(|(section_name, section)| if section_name == name { Some(section) } else { None }),
+            None => self.global_section.as_mut(),
+        }
+    }
+
+    /// Like [`OwnedIniFile::section_mut`], adding the section at the end of the file when it does not exist yet.
+    pub fn section_mut_or_insert(&mut self, name: Option<&str>) -> &mut OwnedIniSection {
+        match name {
+            Some(name) => {
+                let index = match self.sections.iter().position(|(section_name, _)| section_name == name) {
+                    Some(index) => index,
+                    None => {
+                        self.sections.push((name.to_string(), OwnedIniSection::default()));
+                        self.sections.len() - 1
+                    }
+                };
+                &mut self.sections[index].1
+            }
+            None => self.global_section.get_or_insert_with(OwnedIniSection::default),
+        }
+    }
+
+    /// The named sections, in the order they appear in the file.
+    pub fn sections(&self) -> impl Iterator<Item = (&str, &OwnedIniSection)> {
+        self.sections.iter().map(|(section_name, section)| (section_name.as_str(), section))
+    }
+
+    pub fn as_ini_file(&self) -> IniFile<'_> {
+        IniFile {
+            global_section: self.global_section.as_ref().map(OwnedIniSection::as_ini_section),
+            sections: self
+                .sections
+                .iter()
+                .map(|(section_name, section)| (section_name.as_str(), section.as_ini_section()))
+                .collect(),
+        }
+    }
+}
+
+impl From<&IniFile<'_>> for OwnedIniFile {
+    fn from(ini_file: &IniFile<'_>) -> Self {
+        Self {
+            global_section: ini_file.get_global_section().map(OwnedIniSection::from),
+            sections: ini_file
+                .sections()
+                .map(|(section_name, section)| (section_name.to_string(), OwnedIniSection::from(section)))
+                .collect(),
+        }
+    }
+}
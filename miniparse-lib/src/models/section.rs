@@ -17,6 +17,39 @@ impl<'content> IniSection<'content> {
             .iter()
             .find_map(|entry| if entry.key == key { Some(entry.value) } else { None })
     }
+
+    /// Looks up the value of a key for a locale like `de_DE.UTF-8@euro`, with the locale suffixed to the key in
+    /// brackets. Like in .desktop files, `key[lang_COUNTRY@MODIFIER]`, `key[lang_COUNTRY]`, `key[lang@MODIFIER]` and
+    /// `key[lang]` are tried in that order, falling back to the plain `key`. The encoding of the locale is ignored.
+    pub fn get_localized(&self, key: &str, locale: &str) -> Option<&'content str> {
+        let (locale, modifier) = match locale.split_once('@') {
+            Some((locale, modifier)) => (locale, Some(modifier)),
+            None => (locale, None),
+        };
+        let locale = locale.split_once('.').map_or(locale, |(locale, _encoding)| locale);
+        let (lang, country) = match locale.split_once('_') {
+            Some((lang, country)) => (lang, Some(country)),
+            None => (locale, None),
+        };
+
+        let mut candidates = Vec::new();
+        if let (Some(country), Some(modifier)) = (country, modifier) {
+            candidates.push(format!("{lang}_{country}@{modifier}"));
+        }
+        if let Some(country) = country {
+            candidates.push(format!("{lang}_{country}"));
+        }
+        if let Some(modifier) = modifier {
+            candidates.push(format!("{lang}@{modifier}"));
+        }
+        candidates.push(lang.to_string());
+
+        candidates
+            .iter()
+            .filter(|candidate| !candidate.is_empty())
+            .find_map(|candidate| self.get_value_by_key(&format!("{key}[{candidate}]")))
+            .or_else(|| self.get_value_by_key(key))
+    }
 }
 
 impl<'content> Display for IniSection<'content> {
@@ -0,0 +1,191 @@
+//! Selecting keys from a parsed ini file with path expressions.
+//!
+//! A query is a `section.key` path, where both parts may contain the wildcards `*` (any text) and `?` (any single
+//! character). A path without a dot selects keys in the global section. Since section names may contain dots
+//! themselves, the path is split at its last dot. The path can be followed by a filter on the value:
+//!
+//! - `path=pattern` keeps the values matching the wildcard pattern
+//! - `path!=pattern` keeps the values not matching it
+//! - `path~regex` keeps the values matching the regular expression
+
+use regex::Regex;
+
+use crate::ParseError;
+use crate::models::{IniFile, IniSection};
+
+#[derive(Debug)]
+enum ValueFilter {
+    Matches(Regex),
+    DoesNotMatch(Regex),
+}
+
+#[derive(Debug)]
+pub struct Query {
+    /// `None` to select the global section
+    section: Option<Regex>,
+    key: Regex,
+    filter: Option<ValueFilter>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct QueryMatch<'content> {
+    /// `None` for the global section
+    pub section: Option<&'content str>,
+    pub key: &'content str,
+    pub value: &'content str,
+}
+
+/// Turns a pattern with `*` and `?` wildcards into a regex matching the whole text.
+fn wildcard_regex(pattern: &str, ignore_case: bool) -> Result<Regex, ParseError> {
+    let mut regex = String::from(if ignore_case { "(?i)^" } else { "^" });
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    Regex::new(&regex).map_err(|error| ParseError::InvalidQuery(error.to_string()))
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, ParseError> {
+        Self::parse_matching(query, false)
+    }
+
+    /// Like [`Query::parse`], matching section names and keys regardless of their case. Value filters stay case-sensitive.
+    pub fn parse_ignore_case(query: &str) -> Result<Self, ParseError> {
+        Self::parse_matching(query, true)
+    }
+
+    fn parse_matching(query: &str, ignore_case: bool) -> Result<Self, ParseError> {
+        // The path ends at the first operator, so the pattern after it may contain operator characters itself
+        let operator = ["!=", "=", "~"]
+            .into_iter()
+            .filter_map(|operator| Some((query.find(operator)?, operator)))
+            .min_by_key(|(position, _)| *position);
+
+        let (path, filter) = match operator {
+            Some((position, operator)) => {
+                let (path, pattern) = (&query[..position], &query[position + operator.len()..]);
+                let filter = match operator {
+                    "!=" => ValueFilter::DoesNotMatch(wildcard_regex(pattern, false)?),
+                    "=" => ValueFilter::Matches(wildcard_regex(pattern, false)?),
+                    _ => ValueFilter::Matches(Regex::new(pattern).map_err(|error| ParseError::InvalidQuery(error.to_string()))?),
+                };
+                (path, Some(filter))
+            }
+            None => (query, None),
+        };
+
+        let (section, key) = match path.rsplit_once('.') {
+            Some((section, key)) => (Some(section), key),
+            None => (None, path),
+        };
+
+        if key.is_empty() || section.is_some_and(str::is_empty) {
+            return Err(ParseError::InvalidQuery(query.to_string()));
+        }
+
+        Ok(Self {
+            section: section.map(|section| wildcard_regex(section, ignore_case)).transpose()?,
+            key: wildcard_regex(key, ignore_case)?,
+            filter,
+        })
+    }
+
+    fn matches_value(&self, value: &str) -> bool {
+        match &self.filter {
+            None => true,
+            Some(ValueFilter::Matches(regex)) => regex.is_match(value),
+            Some(ValueFilter::DoesNotMatch(regex)) => !regex.is_match(value),
+        }
+    }
+
+    fn run_on_section<'content>(&self, section_name: Option<&'content str>, section: &IniSection<'content>, matches: &mut Vec<QueryMatch<'content>>) {
+        for entry in &section.entries {
+            if self.key.is_match(entry.key) && self.matches_value(entry.value) {
+                matches.push(QueryMatch {
+                    section: section_name,
+                    key: entry.key,
+                    value: entry.value,
+                });
+            }
+        }
+    }
+
+    /// Every entry matching the query, in file order. Repeated keys match once per occurrence.
+    pub fn run<'content>(&self, ini_file: &IniFile<'content>) -> Vec<QueryMatch<'content>> {
+        let mut matches = Vec::new();
+
+        match &self.section {
+            None => {
+                if let Some(global_section) = ini_file.get_global_section() {
+                    self.run_on_section(None, global_section, &mut matches);
+                }
+            }
+            Some(section_regex) => {
+                for (section_name, section) in ini_file.sections() {
+                    if section_regex.is_match(section_name) {
+                        self.run_on_section(Some(section_name), section, &mut matches);
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+    use crate::query::Query;
+
+    const INI: &str = "name=app\n[db]\nhost=localhost\nport=5432\n[db.replica]\nhost=replica\nport=5433\n[cache]\nttl=60\n";
+
+    fn run(query: &str) -> Vec<String> {
+        let ini_file = parse(INI).unwrap();
+        Query::parse(query)
+            .unwrap()
+            .run(&ini_file)
+            .iter()
+            .map(|found| format!("{}.{}={}", found.section.unwrap_or_default(), found.key, found.value))
+            .collect()
+    }
+
+    #[test]
+    fn wildcards_match_sections_and_keys() {
+        assert_eq!(run("db*.host"), vec!["db.host=localhost", "db.replica.host=replica"]);
+        assert_eq!(run("cache.*"), vec!["cache.ttl=60"]);
+        assert_eq!(run("nam?"), vec![".name=app"]);
+    }
+
+    #[test]
+    fn values_are_filtered() {
+        assert_eq!(run("*.port=5433"), vec!["db.replica.port=5433"]);
+        assert_eq!(run("*.host!=local*"), vec!["db.replica.host=replica"]);
+        assert_eq!(run("*.*~^[0-9]+$"), vec!["db.port=5432", "db.replica.port=5433", "cache.ttl=60"]);
+    }
+
+    #[test]
+    fn patterns_may_contain_operators() {
+        assert_eq!(run("*.host~^(?:replica|b=)$"), vec!["db.replica.host=replica"]);
+        assert_eq!(run("*.host!=~*"), vec!["db.host=localhost", "db.replica.host=replica"]);
+    }
+
+    #[test]
+    fn empty_parts_are_invalid() {
+        assert!(Query::parse("db.").is_err());
+        assert!(Query::parse(".key").is_err());
+    }
+
+    #[test]
+    fn ignore_case_matches_any_case() {
+        let ini_file = parse("[Database]\nHost=localhost\n").unwrap();
+        assert_eq!(Query::parse_ignore_case("database.host").unwrap().run(&ini_file).len(), 1);
+        assert!(Query::parse("database.host").unwrap().run(&ini_file).is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! Fixes for common syntax mistakes that make lines unparsable, or unreadable for other tools.
+
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// An entry, split around its delimiter, which may be repeated
+static ENTRY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<key>\s*[^=\s;#\[][^=]*?)(?P<before>\s*)(?P<delimiter>=+)(?P<after>\s*)(?P<value>.*)$").expect("Invalid regex!")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixKind {
+    /// A byte order mark was removed
+    ByteOrderMark,
+    /// A `]` was added to a section header that was not closed
+    UnclosedSectionHeader,
+    /// A delimiter typed as `==` was replaced by `=`
+    RepeatedDelimiter,
+    /// Tabs around the delimiter of an entry were replaced by a space
+    TabsAroundDelimiter,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fix {
+    /// 1-based line number
+    pub line: usize,
+    pub kind: FixKind,
+}
+
+impl Display for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            FixKind::ByteOrderMark => write!(f, "Removed a byte order mark"),
+            FixKind::UnclosedSectionHeader => write!(f, "Closed the section header with `]`"),
+            FixKind::RepeatedDelimiter => write!(f, "Replaced `==` by `=`"),
+            FixKind::TabsAroundDelimiter => write!(f, "Replaced tabs around `=` by spaces"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Repair {
+    /// The input with every fix applied
+    pub text: String,
+    /// The applied fixes, ordered by line number
+    pub fixes: Vec<Fix>,
+}
+
+fn repair_line(line: &str, line_number: usize, fixes: &mut Vec<Fix>) -> String {
+    let mut fix = |kind| fixes.push(Fix { line: line_number, kind });
+
+    let line = match line.strip_prefix('\u{feff}') {
+        Some(line) => {
+            fix(FixKind::ByteOrderMark);
+            line
+        }
+        None => line,
+    };
+
+    let trimmed = line.trim_end();
+    if trimmed.trim_start().starts_with('[') {
+        if trimmed.contains(']') {
+            return line.to_string();
+        }
+        fix(FixKind::UnclosedSectionHeader);
+        return format!("{trimmed}]");
+    }
+
+    let Some(captures) = ENTRY_REGEX.captures(line) else {
+        return line.to_string();
+    };
+
+    let mut delimiter = &captures["delimiter"];
+    if delimiter.len() > 1 {
+        fix(FixKind::RepeatedDelimiter);
+        delimiter = "=";
+    }
+
+    let (mut before, mut after) = (&captures["before"], &captures["after"]);
+    if before.contains('\t') || after.contains('\t') {
+        fix(FixKind::TabsAroundDelimiter);
+        before = if before.is_empty() { "" } else { " " };
+        after = if after.is_empty() { "" } else { " " };
+    }
+
+    format!("{}{before}{delimiter}{after}{}", &captures["key"], &captures["value"])
+}
+
+/// Fixes the mistakes in the ini string that can be fixed without guessing: a byte order mark, section headers missing
+/// their `]`, delimiters typed as `==`, and tabs around delimiters. Comments and other lines are left as they are.
+pub fn repair(ini_string: &str) -> Repair {
+    let mut text = String::with_capacity(ini_string.len());
+    let mut fixes = Vec::new();
+
+    for (index, line) in ini_string.split_inclusive('\n').enumerate() {
+        let (line, line_ending) = match line.strip_suffix("\r\n") {
+            Some(line) => (line, "\r\n"),
+            None => line.strip_suffix('\n').map_or((line, ""), |line| (line, "\n")),
+        };
+
+        text.push_str(&repair_line(line, index + 1, &mut fixes));
+        text.push_str(line_ending);
+    }
+
+    Repair { text, fixes }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+    use crate::repair::{FixKind, repair};
+
+    #[test]
+    fn mistakes_are_fixed_and_reported() {
+        let repaired = repair("\u{feff}name==demo\r\n[server\r\nport\t=\t80\r\n; a == comment\r\n");
+
+        assert_eq!(repaired.text, "name=demo\r\n[server]\r\nport = 80\r\n; a == comment\r\n");
+        let fixes = repaired.fixes.iter().map(|fix| (fix.line, fix.kind)).collect::<Vec<_>>();
+        assert_eq!(
+            fixes,
+            vec![
+                (1, FixKind::ByteOrderMark),
+                (1, FixKind::RepeatedDelimiter),
+                (2, FixKind::UnclosedSectionHeader),
+                (3, FixKind::TabsAroundDelimiter),
+            ]
+        );
+
+        let ini_file = parse(&repaired.text).unwrap();
+        assert_eq!(ini_file.get_section_by_name("server").unwrap().get_value_by_key("port"), Some("80"));
+    }
+
+    #[test]
+    fn valid_files_are_left_alone() {
+        let ini_string = "a = 1\n\n[s]\nb=2\n";
+        let repaired = repair(ini_string);
+
+        assert_eq!(repaired.text, ini_string);
+        assert!(repaired.fixes.is_empty());
+    }
+}
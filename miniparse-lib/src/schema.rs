@@ -0,0 +1,437 @@
+//! Validation of ini strings against a schema of expected keys and value types.
+//!
+//! A schema is itself an ini file. Every entry names a key and its type, and the section it is in is the section the
+//! key is expected in. Types are `string`, `bool`, `int` and `float`. A key is required unless its type ends in `?`.
+//!
+//! A key that was renamed is declared as `deprecated:` followed by its replacement. Lookups with [`IniSchema::get`] of
+//! the replacement fall back to the deprecated key, and validation accepts it in place of the replacement.
+//!
+//! ```ini
+//! name = string
+//!
+//! [server]
+//! port = int
+//! debug = bool?
+//! hostname = string
+//! host = deprecated:hostname
+//! ```
+
+use std::fmt::Display;
+
+use crate::lines::{LineKind, describe_section, split_lines};
+use crate::models::{IniFile, IniSection};
+use crate::{ParseError, parse};
+
+pub(crate) const DEPRECATED_PREFIX: &str = "deprecated:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    String,
+    Bool,
+    Int,
+    Float,
+}
+
+impl ValueType {
+    pub fn accepts(self, value: &str) -> bool {
+        match self {
+            Self::String => true,
+            Self::Bool => matches!(value.to_lowercase().as_str(), "true" | "false" | "yes" | "no" | "on" | "off" | "1" | "0"),
+            Self::Int => value.parse::<i64>().is_ok(),
+            Self::Float => value.parse::<f64>().is_ok(),
+        }
+    }
+}
+
+impl TryFrom<&str> for ValueType {
+    type Error = ParseError;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        match name {
+            "string" => Ok(Self::String),
+            "bool" => Ok(Self::Bool),
+            "int" => Ok(Self::Int),
+            "float" => Ok(Self::Float),
+            _ => Err(ParseError::UnknownValueType(name.to_string())),
+        }
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String => write!(f, "string"),
+            Self::Bool => write!(f, "bool"),
+            Self::Int => write!(f, "int"),
+            Self::Float => write!(f, "float"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KeySchema<'content> {
+    pub key: &'content str,
+    pub value_type: ValueType,
+    pub required: bool,
+}
+
+/// A key that was renamed, and is still read in place of its replacement
+#[derive(Debug)]
+pub struct Deprecation<'content> {
+    pub key: &'content str,
+    pub replacement: &'content str,
+}
+
+#[derive(Debug, Default)]
+pub struct IniSchema<'content> {
+    /// Schemas of the keys per section, `None` being the global section. Sorted by section name.
+    pub sections: Vec<(Option<&'content str>, Vec<KeySchema<'content>>)>,
+    /// Deprecated keys per section, in the same order as `sections`
+    pub deprecations: Vec<(Option<&'content str>, Vec<Deprecation<'content>>)>,
+}
+
+/// Reported when a deprecated key is used instead of its replacement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecationWarning {
+    /// 1-based line number of the deprecated key, when known
+    pub line: Option<usize>,
+    pub section: Option<String>,
+    pub key: String,
+    pub replacement: String,
+}
+
+impl Display for DeprecationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let section = describe_section(self.section.as_deref());
+        write!(f, "Key {} in {section} is deprecated, use {} instead", self.key, self.replacement)
+    }
+}
+
+/// A value found by [`IniSchema::get`]
+#[derive(Debug, PartialEq, Eq)]
+pub struct SchemaLookup<'content> {
+    pub value: &'content str,
+    /// Set when the value was read from a deprecated key
+    pub deprecation: Option<DeprecationWarning>,
+}
+
+#[derive(Debug)]
+pub enum ViolationKind {
+    MissingSection,
+    MissingKey,
+    /// A section that the schema does not describe. Only reported by [`IniSchema::unknown_sections`].
+    UnknownSection,
+    InvalidValue {
+        expected: ValueType,
+        value: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct Violation {
+    /// 1-based line number the violation was found at. Missing sections have no line.
+    pub line: Option<usize>,
+    pub section: Option<String>,
+    pub key: Option<String>,
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let section = describe_section(self.section.as_deref());
+        let key = self.key.as_deref().unwrap_or_default();
+
+        match &self.kind {
+            ViolationKind::MissingSection => write!(f, "Section {section} is missing"),
+            ViolationKind::MissingKey => write!(f, "Required key {key} is missing from {section}"),
+            ViolationKind::UnknownSection => write!(f, "Section {section} is not in the schema"),
+            ViolationKind::InvalidValue { expected, value } => write!(f, "Key {key} in {section} should be of type {expected}, found {value:?}"),
+        }
+    }
+}
+
+fn parse_key_schema<'content>(key: &'content str, type_name: &str) -> Result<KeySchema<'content>, ParseError> {
+    let (type_name, required) = match type_name.strip_suffix('?') {
+        Some(type_name) => (type_name, false),
+        None => (type_name, true),
+    };
+
+    Ok(KeySchema {
+        key,
+        value_type: ValueType::try_from(type_name)?,
+        required,
+    })
+}
+
+type SectionSchema<'content> = (Vec<KeySchema<'content>>, Vec<Deprecation<'content>>);
+
+fn parse_section_schema<'content>(section: &IniSection<'content>) -> Result<SectionSchema<'content>, ParseError> {
+    let mut keys = Vec::new();
+    let mut deprecations = Vec::new();
+
+    for entry in section.entries.iter() {
+        match entry.value.strip_prefix(DEPRECATED_PREFIX) {
+            Some(replacement) => deprecations.push(Deprecation { key: entry.key, replacement }),
+            None => keys.push(parse_key_schema(entry.key, entry.value)?),
+        }
+    }
+
+    Ok((keys, deprecations))
+}
+
+impl<'content> IniSchema<'content> {
+    pub fn parse(schema_string: &'content str) -> Result<Self, ParseError> {
+        let schema_file = parse(schema_string)?;
+        let mut schema = Self::default();
+
+        let mut named_sections = schema_file.sections().map(|(name, section)| (Some(name), section)).collect::<Vec<_>>();
+        named_sections.sort_by_key(|(name, _)| *name);
+        let sections = schema_file
+            .get_global_section()
+            .map(|section| (None, section))
+            .into_iter()
+            .chain(named_sections);
+
+        for (name, section) in sections {
+            let (keys, deprecations) = parse_section_schema(section)?;
+            schema.sections.push((name, keys));
+            schema.deprecations.push((name, deprecations));
+        }
+
+        Ok(schema)
+    }
+
+    /// The deprecated keys that are read in place of the key, in the order they are declared.
+    pub fn deprecated_keys(&self, section: Option<&str>, key: &str) -> impl Iterator<Item = &'content str> {
+        self.deprecations
+            .iter()
+            .filter(move |(deprecation_section, _)| *deprecation_section == section)
+            .flat_map(|(_, deprecations)| deprecations)
+            .filter(move |deprecation| deprecation.replacement == key)
+            .map(|deprecation| deprecation.key)
+    }
+
+    /// Looks up the value of a key, falling back to the keys it replaces when it is missing. A value read from a
+    /// deprecated key is logged as a warning, and comes with a [`DeprecationWarning`] for the caller to report.
+    pub fn get<'file>(&self, ini_file: &IniFile<'file>, section: Option<&str>, key: &str) -> Option<SchemaLookup<'file>> {
+        let found_section = match section {
+            Some(name) => ini_file.get_section_by_name(name),
+            None => ini_file.get_global_section(),
+        }?;
+
+        if let Some(value) = found_section.get_value_by_key(key) {
+            return Some(SchemaLookup { value, deprecation: None });
+        }
+
+        self.deprecated_keys(section, key).find_map(|deprecated_key| {
+            let value = found_section.get_value_by_key(deprecated_key)?;
+            let warning = DeprecationWarning {
+                line: None,
+                section: section.map(str::to_string),
+                key: deprecated_key.to_string(),
+                replacement: key.to_string(),
+            };
+            log::warn!("{warning}");
+
+            Some(SchemaLookup {
+                value,
+                deprecation: Some(warning),
+            })
+        })
+    }
+
+    /// Finds every use of a deprecated key in the ini string, ordered by line number.
+    pub fn deprecations(&self, ini_string: &str) -> Result<Vec<DeprecationWarning>, ParseError> {
+        let lines = split_lines(ini_string)?;
+        let mut warnings = Vec::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            let LineKind::Entry { key, .. } = &line.kind else {
+                continue;
+            };
+
+            let deprecation = self
+                .deprecations
+                .iter()
+                .filter(|(section, _)| *section == line.section)
+                .flat_map(|(_, deprecations)| deprecations)
+                .find(|deprecation| deprecation.key == *key);
+
+            if let Some(deprecation) = deprecation {
+                warnings.push(DeprecationWarning {
+                    line: Some(index + 1),
+                    section: line.section.map(str::to_string),
+                    key: key.to_string(),
+                    replacement: deprecation.replacement.to_string(),
+                });
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Finds every header of a section that the schema does not describe, ordered by line number. Unlike missing keys,
+    /// these are not violations of the schema by themselves, but strict checks can treat them as such.
+    pub fn unknown_sections(&self, ini_string: &str) -> Result<Vec<Violation>, ParseError> {
+        let lines = split_lines(ini_string)?;
+
+        let violations = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| matches!(line.kind, LineKind::SectionHeader { .. }))
+            .filter(|(_, line)| !self.sections.iter().any(|(section, _)| *section == line.section))
+            .map(|(index, line)| Violation {
+                line: Some(index + 1),
+                section: line.section.map(str::to_string),
+                key: None,
+                kind: ViolationKind::UnknownSection,
+            });
+
+        Ok(violations.collect())
+    }
+
+    /// Checks the ini string against the schema, returning every violation ordered by line number.
+    pub fn validate(&self, ini_string: &str) -> Result<Vec<Violation>, ParseError> {
+        let lines = split_lines(ini_string)?;
+        let mut violations = Vec::new();
+
+        for (section, keys) in &self.sections {
+            let header_line = lines
+                .iter()
+                .position(|line| line.section == *section && matches!(line.kind, LineKind::SectionHeader { .. }))
+                .map(|index| index + 1);
+
+            if section.is_some() && header_line.is_none() {
+                if keys.iter().any(|key_schema| key_schema.required) {
+                    violations.push(Violation {
+                        line: None,
+                        section: section.map(str::to_string),
+                        key: None,
+                        kind: ViolationKind::MissingSection,
+                    });
+                }
+                continue;
+            }
+
+            for key_schema in keys {
+                // Deprecated keys count as the key they were replaced by
+                let deprecated_keys = self.deprecated_keys(*section, key_schema.key).collect::<Vec<_>>();
+                let entries = lines.iter().enumerate().filter_map(|(index, line)| match &line.kind {
+                    LineKind::Entry { key, .. } if line.section == *section && (*key == key_schema.key || deprecated_keys.contains(key)) => {
+                        Some((index, *key, line))
+                    }
+                    _ => None,
+                });
+
+                let mut found = false;
+                for (index, key, line) in entries {
+                    found = true;
+                    let value = line.value().unwrap_or_default();
+
+                    if !key_schema.value_type.accepts(value) {
+                        violations.push(Violation {
+                            line: Some(index + 1),
+                            section: section.map(str::to_string),
+                            key: Some(key.to_string()),
+                            kind: ViolationKind::InvalidValue {
+                                expected: key_schema.value_type,
+                                value: value.to_string(),
+                            },
+                        });
+                    }
+                }
+
+                if !found && key_schema.required {
+                    violations.push(Violation {
+                        line: header_line,
+                        section: section.map(str::to_string),
+                        key: Some(key_schema.key.to_string()),
+                        kind: ViolationKind::MissingKey,
+                    });
+                }
+            }
+        }
+
+        violations.sort_by_key(|violation| violation.line);
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+    use crate::schema::{IniSchema, ViolationKind};
+
+    const SCHEMA: &str = "name = string\n[server]\nport = int\ndebug = bool?\n[client]\nretries = int\n";
+
+    #[test]
+    fn valid_file_has_no_violations() {
+        let schema = IniSchema::parse(SCHEMA).unwrap();
+        let violations = schema.validate("name=demo\n[server]\nport=80\n[client]\nretries=3\n").unwrap();
+        assert!(violations.is_empty());
+    }
+
+    #[test]
+    fn invalid_value_is_reported_with_line() {
+        let schema = IniSchema::parse(SCHEMA).unwrap();
+        let violations = schema
+            .validate("name=demo\n[server]\nport=80\ndebug=maybe\n[client]\nretries=3\n")
+            .unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(4));
+        assert!(matches!(violations[0].kind, ViolationKind::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn missing_key_and_section_are_reported() {
+        let schema = IniSchema::parse(SCHEMA).unwrap();
+        let violations = schema.validate("[server]\nport=80\n").unwrap();
+        assert_eq!(violations.len(), 2);
+        assert!(matches!(violations[0].kind, ViolationKind::MissingKey));
+        assert_eq!(violations[0].line, None);
+        assert!(matches!(violations[1].kind, ViolationKind::MissingSection));
+    }
+
+    #[test]
+    fn unknown_sections_are_reported_with_line() {
+        let schema = IniSchema::parse(SCHEMA).unwrap();
+        let violations = schema.unknown_sections("name=demo\n[server]\nport=80\n[cache]\nttl=60\n").unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(4));
+        assert_eq!(violations[0].to_string(), "Section [cache] is not in the schema");
+    }
+
+    #[test]
+    fn unknown_type_is_an_error() {
+        assert!(IniSchema::parse("port = integer").is_err());
+    }
+
+    #[test]
+    fn deprecated_keys_stand_in_for_their_replacement() {
+        let schema = IniSchema::parse("[server]\nhostname = string\nport = int\nhost = deprecated:hostname\nlisten = deprecated:port\n").unwrap();
+
+        let violations = schema.validate("[server]\nhost=localhost\nlisten=eighty\n").unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].key.as_deref(), Some("listen"));
+
+        let warnings = schema.deprecations("[server]\nhost=localhost\nport=80\n").unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, Some(2));
+        assert_eq!(warnings[0].to_string(), "Key host in [server] is deprecated, use hostname instead");
+    }
+
+    #[test]
+    fn get_falls_back_to_deprecated_keys() {
+        let schema = IniSchema::parse("[server]\nhostname = string\nhost = deprecated:hostname\n").unwrap();
+
+        let ini_file = parse("[server]\nhost=old\n").unwrap();
+        let lookup = schema.get(&ini_file, Some("server"), "hostname").unwrap();
+        assert_eq!(lookup.value, "old");
+        assert_eq!(lookup.deprecation.unwrap().key, "host");
+
+        let ini_file = parse("[server]\nhost=old\nhostname=new\n").unwrap();
+        let lookup = schema.get(&ini_file, Some("server"), "hostname").unwrap();
+        assert_eq!(lookup.value, "new");
+        assert!(lookup.deprecation.is_none());
+    }
+}
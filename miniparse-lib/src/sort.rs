@@ -0,0 +1,176 @@
+//! Alphabetical ordering of sections and keys, keeping comments attached to what they describe.
+//!
+//! Comment lines directly above a section header or an entry, without a blank line in between, are considered to
+//! belong to it and move along with it. All other lines keep their place.
+
+use std::cmp::Ordering;
+
+use crate::ParseError;
+use crate::lines::{Line, LineKind, attached_comments_start, detect_line_ending, join_lines, new_line, split_lines};
+
+fn compare_names(a: &str, b: &str) -> Ordering {
+    a.to_lowercase().cmp(&b.to_lowercase()).then_with(|| a.cmp(b))
+}
+
+/// Splits the lines into the lines before the first section, and one block of lines per section.
+fn split_section_blocks(mut lines: Vec<Line<'_>>) -> (Vec<Line<'_>>, Vec<Vec<Line<'_>>>) {
+    let block_starts: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| matches!(line.kind, LineKind::SectionHeader { .. }))
+        .map(|(index, _)| attached_comments_start(&lines, index))
+        .collect();
+
+    let mut blocks = Vec::new();
+    for block_start in block_starts.into_iter().rev() {
+        blocks.push(lines.split_off(block_start));
+    }
+    blocks.reverse();
+
+    (lines, blocks)
+}
+
+/// Prepares the lines for reordering, returning whether the file ended with a line ending.
+fn normalize_line_endings(lines: &mut [Line<'_>]) -> bool {
+    let line_ending = detect_line_ending(lines);
+    let ends_with_line_ending = lines.last().is_some_and(|line| !line.ending.is_empty());
+
+    for line in lines.iter_mut() {
+        line.ending = line_ending;
+    }
+
+    ends_with_line_ending
+}
+
+fn restore_final_line_ending(lines: &mut [Line<'_>], ends_with_line_ending: bool) {
+    if let Some(last_line) = lines.last_mut()
+        && !ends_with_line_ending
+    {
+        last_line.ending = "";
+    }
+}
+
+/// Orders the sections by name. The global section stays at the top.
+pub fn sort_sections(ini_string: &str) -> Result<String, ParseError> {
+    let mut lines = split_lines(ini_string)?;
+    let ends_with_line_ending = normalize_line_endings(&mut lines);
+    let line_ending = detect_line_ending(&lines);
+
+    let (mut sorted, mut blocks) = split_section_blocks(lines);
+
+    // Blank lines at the end of a block separate it from the next one. Strip them, and put a single blank line
+    // between all blocks after sorting if the file used separators at all.
+    let mut separated = false;
+    for block in blocks.iter_mut() {
+        while block.last().is_some_and(Line::is_blank) {
+            block.pop();
+            separated = true;
+        }
+    }
+
+    blocks.sort_by(|a, b| {
+        compare_names(
+            a.iter().find_map(Line::section_name).unwrap_or_default(),
+            b.iter().find_map(Line::section_name).unwrap_or_default(),
+        )
+    });
+
+    for (index, block) in blocks.into_iter().enumerate() {
+        if index > 0 && separated {
+            sorted.push(new_line(String::new(), line_ending, None));
+        }
+        sorted.extend(block);
+    }
+
+    restore_final_line_ending(&mut sorted, ends_with_line_ending);
+    Ok(join_lines(&sorted))
+}
+
+enum Slot<'content> {
+    Fixed(Line<'content>),
+    Entry,
+}
+
+/// Orders the entries of a section body by key, keeping all other lines in place.
+fn sort_body(body: Vec<Line<'_>>) -> Vec<Line<'_>> {
+    let mut slots = Vec::new();
+    let mut entries: Vec<Vec<Line<'_>>> = Vec::new();
+    let mut pending_comments = Vec::new();
+
+    for line in body {
+        match line.kind {
+            LineKind::Entry { .. } => {
+                pending_comments.push(line);
+                entries.push(std::mem::take(&mut pending_comments));
+                slots.push(Slot::Entry);
+            }
+            _ if line.is_comment() => pending_comments.push(line),
+            _ => {
+                slots.extend(pending_comments.drain(..).map(Slot::Fixed));
+                slots.push(Slot::Fixed(line));
+            }
+        }
+    }
+    slots.extend(pending_comments.drain(..).map(Slot::Fixed));
+
+    let entry_key = |entry: &Vec<Line<'_>>| match entry.last().map(|line| &line.kind) {
+        Some(LineKind::Entry { key, .. }) => key.to_string(),
+        _ => String::new(),
+    };
+    entries.sort_by(|a, b| compare_names(&entry_key(a), &entry_key(b)));
+
+    let mut sorted_entries = entries.into_iter();
+    slots
+        .into_iter()
+        .flat_map(|slot| match slot {
+            Slot::Fixed(line) => vec![line],
+            Slot::Entry => sorted_entries.next().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Orders the entries within every section by key. The order of the sections themselves is kept.
+pub fn sort_keys(ini_string: &str) -> Result<String, ParseError> {
+    let mut lines = split_lines(ini_string)?;
+    let ends_with_line_ending = normalize_line_endings(&mut lines);
+
+    let (global_lines, blocks) = split_section_blocks(lines);
+    let mut sorted = sort_body(global_lines);
+
+    for mut block in blocks {
+        let header_index = block
+            .iter()
+            .position(|line| matches!(line.kind, LineKind::SectionHeader { .. }))
+            .unwrap_or_default();
+        let body = block.split_off(header_index + 1);
+
+        sorted.extend(block);
+        sorted.extend(sort_body(body));
+    }
+
+    restore_final_line_ending(&mut sorted, ends_with_line_ending);
+    Ok(join_lines(&sorted))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sort::{sort_keys, sort_sections};
+
+    #[test]
+    fn sections_are_sorted_with_their_comments() {
+        let sorted = sort_sections("g = 1\n\n; about c\n[c]\nx = 1\n\n[a]\ny = 2\n\n[B]\nz = 3\n").unwrap();
+        assert_eq!(sorted, "g = 1\n\n[a]\ny = 2\n\n[B]\nz = 3\n\n; about c\n[c]\nx = 1\n");
+    }
+
+    #[test]
+    fn sections_without_separators_stay_without_separators() {
+        let sorted = sort_sections("[b]\nx = 1\n[a]\ny = 2").unwrap();
+        assert_eq!(sorted, "[a]\ny = 2\n[b]\nx = 1");
+    }
+
+    #[test]
+    fn keys_are_sorted_with_their_comments() {
+        let sorted = sort_keys("; file comment\n\nc = 3\n; about a\na = 1\n[s]\nz = 1\n\ny = 2\nx = 3\n").unwrap();
+        assert_eq!(sorted, "; file comment\n\n; about a\na = 1\nc = 3\n[s]\nx = 3\n\ny = 2\nz = 1\n");
+    }
+}
@@ -0,0 +1,62 @@
+//! Filling in `{{section.key}}` placeholders with values from an ini file.
+
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+use crate::ParseError;
+use crate::models::IniFile;
+
+static PLACEHOLDER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{\s*([^{}\s]+)\s*\}\}").expect("Invalid regex!"));
+
+/// Looks up a `section.key` path, or just `key` for the global section. Since section names may contain dots
+/// themselves, the path is split at its last dot.
+fn look_up<'content>(ini_file: &IniFile<'content>, path: &str) -> Option<&'content str> {
+    match path.rsplit_once('.') {
+        Some((section_name, key)) => ini_file.get_section_by_name(section_name)?.get_value_by_key(key),
+        None => ini_file.get_global_section()?.get_value_by_key(path),
+    }
+}
+
+/// Replaces every `{{section.key}}` placeholder in the template with its value. Whitespace inside the braces is allowed.
+///
+/// Fails with all placeholders that could not be resolved, rather than leaving them in the output.
+pub fn render(template: &str, ini_file: &IniFile<'_>) -> Result<String, ParseError> {
+    let mut unresolved = Vec::new();
+
+    let rendered = PLACEHOLDER_REGEX.replace_all(template, |captures: &Captures| {
+        let path = &captures[1];
+        look_up(ini_file, path).unwrap_or_else(|| {
+            if !unresolved.contains(&path.to_string()) {
+                unresolved.push(path.to_string());
+            }
+            ""
+        })
+    });
+
+    if !unresolved.is_empty() {
+        return Err(ParseError::UnresolvedPlaceholders(unresolved.join(", ")));
+    }
+
+    Ok(rendered.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+    use crate::template::render;
+
+    #[test]
+    fn placeholders_are_replaced() {
+        let ini_file = parse("name=app\n[db]\nhost=localhost\n[db.replica]\nhost=replica\n").unwrap();
+        let rendered = render("{{name}}: {{ db.host }}, {{db.replica.host}} {not}", &ini_file).unwrap();
+        assert_eq!(rendered, "app: localhost, replica {not}");
+    }
+
+    #[test]
+    fn unresolved_placeholders_are_an_error() {
+        let ini_file = parse("[db]\nhost=localhost\n").unwrap();
+        let error = render("{{db.port}} {{db.host}} {{user}} {{db.port}}", &ini_file).unwrap_err();
+        assert_eq!(error.to_string(), "Unresolved placeholders: db.port, user");
+    }
+}
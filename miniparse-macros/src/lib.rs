@@ -0,0 +1,99 @@
+//! Compile-time embedding of ini files.
+//!
+//! `include_ini!("defaults.ini")` reads the file relative to the manifest directory of the crate that uses it, parses it
+//! strictly, and expands to a [`miniparse::embedded::EmbeddedIni`]. Syntax errors, duplicate keys and repeated sections
+//! fail the build, so shipped defaults are checked before they are released.
+//!
+//! ```ignore
+//! use miniparse::embedded::EmbeddedIni;
+//!
+//! static DEFAULTS: EmbeddedIni = miniparse_macros::include_ini!("defaults.ini");
+//! ```
+
+use std::{fs, path::PathBuf};
+
+use miniparse::models::IniSection;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{LitStr, parse_macro_input};
+
+/// Embeds an ini file, parsed at compile time. See the crate documentation.
+#[proc_macro]
+pub fn include_ini(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+
+    expand(&path, PathBuf::from(manifest_dir).join(path.value()))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn section_tokens(section: &IniSection<'_>) -> TokenStream2 {
+    let (keys, values): (Vec<_>, Vec<_>) = section.entries.iter().map(|entry| (entry.key, entry.value)).unzip();
+    quote! { &[#((#keys, #values)),*] }
+}
+
+fn expand(path: &LitStr, full_path: PathBuf) -> syn::Result<TokenStream2> {
+    let error = |message: String| syn::Error::new(path.span(), format!("{}: {message}", full_path.display()));
+
+    let contents = fs::read_to_string(&full_path).map_err(|err| error(err.to_string()))?;
+    let ini_file = miniparse::parse_strict(&contents).map_err(|err| error(err.to_string()))?;
+
+    let global_section = ini_file.get_global_section().map_or_else(|| quote! { &[] }, section_tokens);
+    let (names, sections): (Vec<_>, Vec<_>) = ini_file.sections().map(|(name, section)| (name, section_tokens(section))).unzip();
+    let full_path = full_path.to_string_lossy();
+
+    Ok(quote! {
+        {
+            // Rebuilds the crate when the file changes
+            const _: &str = include_str!(#full_path);
+
+            ::miniparse::embedded::EmbeddedIni {
+                global_section: #global_section,
+                sections: &[#((#names, #sections)),*],
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use syn::LitStr;
+
+    use crate::expand;
+
+    fn expand_file(name: &str, contents: &str) -> syn::Result<String> {
+        let full_path = std::env::temp_dir().join(format!("miniparse-macros-{}-{name}", std::process::id()));
+        fs::write(&full_path, contents).unwrap();
+
+        let path = LitStr::new(name, proc_macro2::Span::call_site());
+        let expanded = expand(&path, full_path.clone()).map(|tokens| tokens.to_string());
+        fs::remove_file(full_path).unwrap();
+        expanded
+    }
+
+    #[test]
+    fn file_expands_to_embedded_ini() {
+        let expanded = expand_file("valid.ini", "name=demo\n[server]\nport=80\n").unwrap();
+
+        assert!(expanded.contains("global_section : & [(\"name\" , \"demo\")]"), "{expanded}");
+        assert!(expanded.contains("sections : & [(\"server\" , & [(\"port\" , \"80\")])]"), "{expanded}");
+    }
+
+    #[test]
+    fn invalid_files_fail_to_expand() {
+        let error = expand_file("invalid.ini", "[server]\nport=80\nport=81\n").unwrap_err();
+        assert!(error.to_string().contains("duplicate key port"), "{error}");
+
+        assert!(
+            expand(
+                &LitStr::new("missing.ini", proc_macro2::Span::call_site()),
+                "/nonexistent/missing.ini".into()
+            )
+            .is_err()
+        );
+    }
+}
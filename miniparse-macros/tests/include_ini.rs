@@ -0,0 +1,18 @@
+use miniparse::embedded::EmbeddedIni;
+use miniparse_macros::include_ini;
+
+static DEFAULTS: EmbeddedIni = include_ini!("tests/fixtures/defaults.ini");
+
+#[test]
+fn embedded_values_are_read_back() {
+    assert_eq!(DEFAULTS.get(None, "name"), Some("demo"));
+    assert_eq!(DEFAULTS.get(Some("server"), "port"), Some("8080"));
+    assert_eq!(DEFAULTS.get(Some("server"), "missing"), None);
+    assert_eq!(DEFAULTS.sections.iter().map(|(name, _)| *name).collect::<Vec<_>>(), ["server", "cache"]);
+}
+
+#[test]
+fn embedded_file_converts_to_an_ini_file() {
+    let ini_file = DEFAULTS.to_ini_file();
+    assert_eq!(ini_file.get_section_by_name("cache").unwrap().get_value_by_key("ttl"), Some("60"));
+}